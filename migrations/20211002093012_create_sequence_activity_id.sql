@@ -0,0 +1,6 @@
+CREATE SEQUENCE activity_id_seq OWNED BY activity.id;
+SELECT setval('activity_id_seq', coalesce(max(id), 0) + 1, false)
+FROM activity;
+ALTER TABLE activity
+ALTER COLUMN id
+SET DEFAULT nextval('activity_id_seq');
@@ -0,0 +1,7 @@
+ALTER TABLE account
+ADD COLUMN currency VARCHAR(3) NOT NULL DEFAULT 'EUR';
+ALTER TABLE activity
+ADD COLUMN currency VARCHAR(3) NOT NULL DEFAULT 'EUR';
+-- Table comments
+COMMENT ON COLUMN account.currency IS 'Account ISO 4217 currency code';
+COMMENT ON COLUMN activity.currency IS 'Activity amount ISO 4217 currency code';
@@ -0,0 +1,9 @@
+ALTER TABLE activity
+ADD COLUMN converted_amount BIGINT,
+ADD COLUMN converted_currency VARCHAR(3),
+ADD CONSTRAINT activity_converted_amount_currency CHECK (
+        (converted_amount IS NULL) = (converted_currency IS NULL)
+    );
+-- Table comments
+COMMENT ON COLUMN activity.converted_amount IS 'Amount received by the target account, when converted';
+COMMENT ON COLUMN activity.converted_currency IS 'Converted amount ISO 4217 currency code';
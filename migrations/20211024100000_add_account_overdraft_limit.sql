@@ -0,0 +1,5 @@
+ALTER TABLE account
+ADD COLUMN overdraft_limit BIGINT,
+ADD CONSTRAINT account_overdraft_limit_positive CHECK (overdraft_limit >= 0);
+-- Table comments
+COMMENT ON COLUMN account.overdraft_limit IS 'How far the balance may go below zero, in minor units of the account currency';
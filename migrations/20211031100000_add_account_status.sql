@@ -0,0 +1,5 @@
+ALTER TABLE account
+ADD COLUMN status VARCHAR(16) NOT NULL DEFAULT 'active',
+ADD CONSTRAINT account_status_valid CHECK (status IN ('active', 'frozen', 'closed'));
+-- Table comments
+COMMENT ON COLUMN account.status IS 'Account lifecycle status: active, frozen or closed';
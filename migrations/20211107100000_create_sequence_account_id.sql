@@ -0,0 +1,6 @@
+CREATE SEQUENCE account_id_seq OWNED BY account.id;
+SELECT setval('account_id_seq', coalesce(max(id), 0) + 1, false)
+FROM account;
+ALTER TABLE account
+ALTER COLUMN id
+SET DEFAULT nextval('account_id_seq');
@@ -0,0 +1,13 @@
+CREATE SEQUENCE customer_id_seq;
+CREATE TABLE customer (
+    id BIGINT NOT NULL PRIMARY KEY DEFAULT nextval('customer_id_seq'),
+    name VARCHAR(255) NOT NULL
+);
+ALTER SEQUENCE customer_id_seq OWNED BY customer.id;
+ALTER TABLE account
+ADD COLUMN customer_id BIGINT REFERENCES customer (id);
+CREATE INDEX account_customer_id_idx ON account (customer_id);
+-- Table comments
+COMMENT ON COLUMN customer.id IS 'Customer ID';
+COMMENT ON COLUMN customer.name IS 'Customer name';
+COMMENT ON COLUMN account.customer_id IS 'ID of the customer owning the account';
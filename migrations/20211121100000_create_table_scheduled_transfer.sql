@@ -0,0 +1,26 @@
+CREATE TABLE scheduled_transfer (
+    id BIGSERIAL NOT NULL PRIMARY KEY,
+    source_account_id BIGINT NOT NULL,
+    target_account_id BIGINT NOT NULL,
+    amount BIGINT NOT NULL,
+    currency VARCHAR(3) NOT NULL,
+    execute_at TIMESTAMPTZ NOT NULL,
+    status VARCHAR(16) NOT NULL DEFAULT 'pending',
+    executed_at TIMESTAMPTZ,
+    failure TEXT,
+    CONSTRAINT scheduled_transfer_status_valid CHECK (
+        status IN ('pending', 'executing', 'executed', 'failed')
+    )
+);
+CREATE INDEX scheduled_transfer_pending_idx ON scheduled_transfer (execute_at)
+WHERE status = 'pending';
+-- Table comments
+COMMENT ON COLUMN scheduled_transfer.id IS 'Scheduled transfer ID';
+COMMENT ON COLUMN scheduled_transfer.source_account_id IS 'Source account ID';
+COMMENT ON COLUMN scheduled_transfer.target_account_id IS 'Target account ID';
+COMMENT ON COLUMN scheduled_transfer.amount IS 'Transfer amount, in minor units of currency';
+COMMENT ON COLUMN scheduled_transfer.currency IS 'Transfer amount ISO 4217 currency code';
+COMMENT ON COLUMN scheduled_transfer.execute_at IS 'Time after which the transfer is executed';
+COMMENT ON COLUMN scheduled_transfer.status IS 'Scheduled transfer status: pending, executing, executed or failed';
+COMMENT ON COLUMN scheduled_transfer.executed_at IS 'Time the transfer was executed or failed';
+COMMENT ON COLUMN scheduled_transfer.failure IS 'Why the transfer failed';
@@ -0,0 +1,16 @@
+CREATE TABLE idempotency_key (
+    key VARCHAR(255) NOT NULL PRIMARY KEY,
+    source_account_id BIGINT NOT NULL,
+    target_account_id BIGINT NOT NULL,
+    amount BIGINT NOT NULL,
+    currency VARCHAR(3) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+CREATE INDEX idempotency_key_created_at_idx ON idempotency_key (created_at);
+-- Table comments
+COMMENT ON COLUMN idempotency_key.key IS 'Idempotency key chosen by the client';
+COMMENT ON COLUMN idempotency_key.source_account_id IS 'Source account ID of the transfer';
+COMMENT ON COLUMN idempotency_key.target_account_id IS 'Target account ID of the transfer';
+COMMENT ON COLUMN idempotency_key.amount IS 'Transfer amount, in minor units of currency';
+COMMENT ON COLUMN idempotency_key.currency IS 'Transfer amount ISO 4217 currency code';
+COMMENT ON COLUMN idempotency_key.created_at IS 'Time the transfer was completed';
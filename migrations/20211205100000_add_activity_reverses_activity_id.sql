@@ -0,0 +1,7 @@
+ALTER TABLE activity
+ADD COLUMN reverses_activity_id BIGINT REFERENCES activity (id);
+-- Each account compensates an activity at most once
+CREATE UNIQUE INDEX activity_reverses_activity_id_idx ON activity (reverses_activity_id, owner_account_id)
+WHERE reverses_activity_id IS NOT NULL;
+-- Table comments
+COMMENT ON COLUMN activity.reverses_activity_id IS 'Activity compensated by this one, when reversing a transfer';
@@ -0,0 +1,8 @@
+CREATE SEQUENCE transfer_id_seq;
+ALTER TABLE activity
+ADD COLUMN transfer_id BIGINT;
+-- Both activities recording a transfer are loaded together
+CREATE INDEX activity_transfer_id_idx ON activity (transfer_id)
+WHERE transfer_id IS NOT NULL;
+-- Table comments
+COMMENT ON COLUMN activity.transfer_id IS 'Transfer shared by the withdrawal and the deposit recording it, unset for activities recorded before transfer ids';
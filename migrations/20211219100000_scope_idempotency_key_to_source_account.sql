@@ -0,0 +1,7 @@
+-- Clients sending from different accounts may choose the same key
+ALTER TABLE idempotency_key
+DROP CONSTRAINT idempotency_key_pkey;
+ALTER TABLE idempotency_key
+ADD PRIMARY KEY (source_account_id, key);
+-- Table comments
+COMMENT ON COLUMN idempotency_key.key IS 'Idempotency key chosen by the client, unique per source account';
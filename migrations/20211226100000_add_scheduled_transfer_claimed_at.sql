@@ -0,0 +1,10 @@
+-- Transfers left executing by a stopped worker are claimed again once their claim expired
+ALTER TABLE scheduled_transfer
+ADD COLUMN claimed_at TIMESTAMPTZ;
+UPDATE scheduled_transfer
+SET claimed_at = now()
+WHERE status = 'executing';
+CREATE INDEX scheduled_transfer_executing_idx ON scheduled_transfer (claimed_at)
+WHERE status = 'executing';
+-- Table comments
+COMMENT ON COLUMN scheduled_transfer.claimed_at IS 'Time a worker last claimed the transfer';
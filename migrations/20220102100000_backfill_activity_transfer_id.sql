@@ -0,0 +1,54 @@
+-- Link the withdrawal and the deposit recorded before transfer ids: both legs share the source,
+-- target and amounts of the transfer and were recorded within the same moment, the k-th
+-- withdrawal of a kind pairing with the k-th deposit of the same kind
+WITH legs AS (
+    SELECT id,
+        timestamp,
+        owner_account_id = source_account_id AS is_withdrawal,
+        source_account_id,
+        target_account_id,
+        amount,
+        currency,
+        converted_amount,
+        converted_currency,
+        reverses_activity_id,
+        row_number() OVER (
+            PARTITION BY owner_account_id = source_account_id,
+            source_account_id,
+            target_account_id,
+            amount,
+            currency,
+            converted_amount,
+            converted_currency,
+            reverses_activity_id
+            ORDER BY timestamp,
+                id
+        ) AS leg_number
+    FROM activity
+    WHERE transfer_id IS NULL
+        AND source_account_id <> target_account_id
+),
+transfers AS (
+    SELECT w.id AS withdrawal_id,
+        d.id AS deposit_id,
+        nextval('transfer_id_seq') AS transfer_id
+    FROM legs w
+        JOIN legs d ON d.source_account_id = w.source_account_id
+        AND d.target_account_id = w.target_account_id
+        AND d.amount = w.amount
+        AND d.currency = w.currency
+        AND d.converted_amount IS NOT DISTINCT FROM w.converted_amount
+        AND d.converted_currency IS NOT DISTINCT FROM w.converted_currency
+        AND d.reverses_activity_id IS NOT DISTINCT FROM w.reverses_activity_id
+        AND d.leg_number = w.leg_number
+    WHERE w.is_withdrawal
+        AND NOT d.is_withdrawal
+        AND d.timestamp BETWEEN w.timestamp - INTERVAL '1 minute'
+        AND w.timestamp + INTERVAL '1 minute'
+)
+UPDATE activity
+SET transfer_id = transfers.transfer_id
+FROM transfers
+WHERE activity.id IN (transfers.withdrawal_id, transfers.deposit_id);
+-- Table comments
+COMMENT ON COLUMN activity.transfer_id IS 'Transfer shared by the withdrawal and the deposit recording it, unset for older activities whose other leg could not be found';
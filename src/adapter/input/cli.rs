@@ -0,0 +1,161 @@
+use std::io::{self, Write};
+
+use shaku::HasComponent;
+
+use crate::{
+    application::port::input::LedgerAuditUseCase, domain::activity::Activity,
+    infrastructure::container,
+};
+
+/// Run the `audit-ledger` command against the configured database, returning the process exit
+/// code: `0` when the ledger is consistent, `1` when it is not, and `2` when the audit fails.
+pub async fn audit_ledger_command() -> i32 {
+    let module = match container::build_module(&rocket::Config::figment()).await {
+        Ok(module) => module,
+        Err(err) => {
+            eprintln!("Unable to configure the application: {}", err);
+            return 2;
+        }
+    };
+
+    let ledger_audit_service: &dyn LedgerAuditUseCase = module.resolve_ref();
+    match audit_ledger(ledger_audit_service, &mut io::stdout()).await {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(err) => {
+            eprintln!("Unable to audit the ledger: {}", err);
+            2
+        }
+    }
+}
+
+/// Audit the ledger, writing the report to `out` and returning whether the ledger is
+/// consistent.
+pub async fn audit_ledger(
+    ledger_audit_service: &dyn LedgerAuditUseCase,
+    out: &mut impl Write,
+) -> anyhow::Result<bool> {
+    let audit = ledger_audit_service.audit_ledger().await?;
+
+    writeln!(
+        out,
+        "Audited {} activities, {} mirrored transfers",
+        audit.activity_count(),
+        audit.pair_count()
+    )?;
+    for orphan in audit.orphans() {
+        writeln!(
+            out,
+            "Orphan activity {}: {} from account {} to account {}, owned by account {}",
+            activity_id(orphan),
+            orphan.money(),
+            orphan.source_account_id().0,
+            orphan.target_account_id().0,
+            orphan.owner_account_id().0
+        )?;
+    }
+    for mismatch in audit.mismatches() {
+        writeln!(
+            out,
+            "Mismatched activities {} and {}: {} withdrawn, {} deposited",
+            activity_id(mismatch.withdrawal()),
+            activity_id(mismatch.deposit()),
+            mismatch.withdrawal().received_money(),
+            mismatch.deposit().received_money()
+        )?;
+    }
+    for imbalance in audit.imbalances() {
+        writeln!(out, "Ledger imbalance of {}", imbalance)?;
+    }
+
+    if audit.is_consistent() {
+        writeln!(out, "Ledger is consistent")?;
+    } else {
+        writeln!(out, "Ledger is inconsistent")?;
+    }
+
+    Ok(audit.is_consistent())
+}
+
+fn activity_id(activity: &Activity) -> String {
+    activity
+        .id()
+        .map_or_else(|| "without id".to_owned(), |id| id.0.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use rocket::tokio;
+
+    use crate::{
+        application::port::input::LedgerAuditError,
+        domain::{
+            account::AccountId,
+            activity::{tests::default_activity, ActivityId},
+            ledger::LedgerAudit,
+            money::tests::eur,
+        },
+    };
+
+    use super::*;
+
+    /// Audits a ledger made of the given activities.
+    pub struct MockLedgerAuditUseCase(Vec<Activity>);
+
+    #[rocket::async_trait]
+    impl LedgerAuditUseCase for MockLedgerAuditUseCase {
+        async fn audit_ledger(&self) -> Result<LedgerAudit, LedgerAuditError> {
+            Ok(LedgerAudit::of(self.0.clone())?)
+        }
+    }
+
+    fn half(id: u64, owner: u64) -> Activity {
+        default_activity()
+            .id(Some(ActivityId(id)))
+            .owner_account_id(AccountId(owner))
+            .source_account_id(AccountId(1))
+            .target_account_id(AccountId(2))
+            .money(eur(500))
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_reports_consistent_ledgers() -> Result<()> {
+        // Given
+        let service = MockLedgerAuditUseCase(vec![half(1, 1), half(2, 2)]);
+        let mut out = vec![];
+
+        // When
+        let consistent = audit_ledger(&service, &mut out).await?;
+
+        // Expect
+        assert!(consistent);
+        assert_eq!(
+            String::from_utf8(out)?,
+            "Audited 2 activities, 1 mirrored transfers\nLedger is consistent\n"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_reports_orphans_and_imbalances() -> Result<()> {
+        // Given
+        let service = MockLedgerAuditUseCase(vec![half(1, 1)]);
+        let mut out = vec![];
+
+        // When
+        let consistent = audit_ledger(&service, &mut out).await?;
+
+        // Expect
+        assert!(!consistent);
+        let report = String::from_utf8(out)?;
+        assert!(report.contains(
+            "Orphan activity 1: 5.00 EUR from account 1 to account 2, owned by account 1\n"
+        ));
+        assert!(report.contains("Ledger imbalance of -5.00 EUR\n"));
+        assert!(report.ends_with("Ledger is inconsistent\n"));
+        Ok(())
+    }
+}
@@ -0,0 +1,1032 @@
+use chrono::{DateTime, Utc};
+use rocket::{
+    http::Status,
+    request::{FromRequest, Outcome},
+    response::status::{Accepted, Created, Custom},
+    serde::{
+        json::{self, Json},
+        Deserialize, Serialize,
+    },
+};
+
+use crate::{
+    application::{
+        port::input::{
+            AccountBalance, CloseAccountUseCase, CreateAccountCommand, CreateAccountUseCase,
+            GetAccountBalanceQuery, LegOutcome, LegStatus, OpenAccountCommand, OpenAccountUseCase,
+            ScheduleTransferCommand, ScheduleTransferUseCase, SendMoneyBatchCommand,
+            SendMoneyBatchUseCase, SendMoneyCommand, SendMoneyUseCase,
+        },
+        MoneyTransferProperties,
+    },
+    domain::{
+        account::{AccountId, AccountStatus},
+        activity::Activity,
+        money::{Currency, Money},
+        scheduled_transfer::ScheduledTransferStatus,
+    },
+    infrastructure::container::Inject,
+};
+
+use super::error::{ApiError, ErrorBody};
+
+/// Optional `Idempotency-Key` header, identifying requests that may be retried safely.
+#[derive(Debug)]
+pub struct IdempotencyKeyHeader<'r>(Option<&'r str>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IdempotencyKeyHeader<'r> {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(request: &'r rocket::Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(Self(request.headers().get_one("Idempotency-Key")))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct TransferRequest {
+    target_account_id: u64,
+    /// Either a decimal string, e.g. `"12.34 EUR"`, or minor units, e.g.
+    /// `{ "amount": 1234, "currency": "EUR" }`.
+    money: Money,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct TransferResponse {
+    source_account_id: u64,
+    target_account_id: u64,
+    amount: i64,
+    currency: &'static str,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct BatchTransferRequest {
+    legs: Vec<TransferRequest>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct BatchTransferResponse {
+    source_account_id: u64,
+    /// Whether every leg was completed: otherwise none was.
+    committed: bool,
+    legs: Vec<TransferLegResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct TransferLegResponse {
+    target_account_id: u64,
+    amount: i64,
+    currency: &'static str,
+    /// One of `completed`, `rolled_back` or `failed`.
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ErrorBody>,
+}
+
+impl From<LegOutcome> for TransferLegResponse {
+    fn from(outcome: LegOutcome) -> Self {
+        let (leg, status) = outcome.into_parts();
+        let (status, error) = match status {
+            LegStatus::Completed => ("completed", None),
+            LegStatus::RolledBack => ("rolled_back", None),
+            LegStatus::Failed(err) => ("failed", Some(ApiError::from(err).into_body())),
+        };
+
+        Self {
+            target_account_id: leg.target_account_id().0,
+            amount: leg.money().amount(),
+            currency: leg.money().currency().code(),
+            status,
+            error,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ScheduleTransferRequest {
+    target_account_id: u64,
+    /// Either a decimal string, e.g. `"12.34 EUR"`, or minor units, e.g.
+    /// `{ "amount": 1234, "currency": "EUR" }`.
+    money: Money,
+    /// RFC 3339 time after which the transfer is executed.
+    execute_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ScheduledTransferResponse {
+    id: u64,
+    source_account_id: u64,
+    target_account_id: u64,
+    amount: i64,
+    currency: &'static str,
+    execute_at: DateTime<Utc>,
+    status: &'static str,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct OpenAccountRequest {
+    /// Id of the new account, generated when missing.
+    id: Option<u64>,
+    /// ISO 4217 currency code.
+    currency: String,
+    /// Overdraft limit, in minor units of `currency`.
+    overdraft_limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct AccountStatusResponse {
+    id: u64,
+    status: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct AccountResponse {
+    id: u64,
+    currency: &'static str,
+    status: &'static str,
+    baseline_balance: i64,
+    balance: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    overdraft_limit: Option<i64>,
+}
+
+impl From<&AccountBalance> for AccountResponse {
+    fn from(balance: &AccountBalance) -> Self {
+        Self {
+            id: balance.account_id().0,
+            currency: balance.balance().currency().code(),
+            status: balance.status().code(),
+            baseline_balance: balance.baseline_balance().amount(),
+            balance: balance.balance().amount(),
+            overdraft_limit: balance.overdraft_limit().map(|limit| limit.amount()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ActivityResponse {
+    id: Option<u64>,
+    timestamp: DateTime<Utc>,
+    owner_account_id: u64,
+    source_account_id: u64,
+    target_account_id: u64,
+    amount: i64,
+    currency: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    converted_amount: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    converted_currency: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reverses_activity_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transfer_id: Option<u64>,
+}
+
+impl From<&Activity> for ActivityResponse {
+    fn from(activity: &Activity) -> Self {
+        Self {
+            id: activity.id().map(|id| id.0),
+            timestamp: *activity.timestamp(),
+            owner_account_id: activity.owner_account_id().0,
+            source_account_id: activity.source_account_id().0,
+            target_account_id: activity.target_account_id().0,
+            amount: activity.money().amount(),
+            currency: activity.money().currency().code(),
+            converted_amount: activity.converted_money().map(Money::amount),
+            converted_currency: activity.converted_money().map(|m| m.currency().code()),
+            reverses_activity_id: activity.reverses_activity_id().map(|id| id.0),
+            transfer_id: activity.transfer_id().map(|id| id.0),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct AccountActivitiesResponse {
+    #[serde(flatten)]
+    account: AccountResponse,
+    since: DateTime<Utc>,
+    activities: Vec<ActivityResponse>,
+}
+
+impl From<&AccountBalance> for AccountActivitiesResponse {
+    fn from(balance: &AccountBalance) -> Self {
+        Self {
+            account: balance.into(),
+            since: *balance.since(),
+            activities: balance.activities().iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[rocket::get("/<account_id>")]
+pub async fn get_account(
+    account_id: u64,
+    get_account_balance_query: Inject<'_, dyn GetAccountBalanceQuery>,
+) -> Result<Json<AccountResponse>, ApiError> {
+    let balance = get_account_balance_query
+        .get_account_balance(AccountId(account_id), None)
+        .await?;
+
+    Ok(Json((&balance).into()))
+}
+
+#[rocket::get("/<account_id>/activities?<since>")]
+pub async fn get_account_activities(
+    account_id: u64,
+    since: Option<&str>,
+    get_account_balance_query: Inject<'_, dyn GetAccountBalanceQuery>,
+) -> Result<Json<AccountActivitiesResponse>, ApiError> {
+    let since = since
+        .map(|since| {
+            DateTime::parse_from_rfc3339(since)
+                .map(|since| since.with_timezone(&Utc))
+                .map_err(|e| ApiError::bad_request(format!("Invalid 'since' date: {}", e)))
+        })
+        .transpose()?;
+
+    let balance = get_account_balance_query
+        .get_account_balance(AccountId(account_id), since)
+        .await?;
+
+    Ok(Json((&balance).into()))
+}
+
+#[rocket::post("/<source_account_id>/transfers", data = "<request>")]
+pub async fn send_money(
+    source_account_id: u64,
+    request: Result<Json<TransferRequest>, json::Error<'_>>,
+    idempotency_key: IdempotencyKeyHeader<'_>,
+    send_money_service: Inject<'_, dyn SendMoneyUseCase>,
+    money_transfer_properties: Inject<'_, dyn MoneyTransferProperties>,
+) -> Result<Json<TransferResponse>, ApiError> {
+    let request = request.map_err(json_error)?;
+
+    let mut cmd = SendMoneyCommand::try_new(
+        AccountId(source_account_id),
+        AccountId(request.target_account_id),
+        request.money,
+        money_transfer_properties.command_ceiling(),
+    )?;
+    if let IdempotencyKeyHeader(Some(key)) = idempotency_key {
+        cmd = cmd.with_idempotency_key(key)?;
+    }
+
+    send_money_service.send_money(cmd).await?;
+
+    Ok(Json(TransferResponse {
+        source_account_id,
+        target_account_id: request.target_account_id,
+        amount: request.money.amount(),
+        currency: request.money.currency().code(),
+    }))
+}
+
+/// Send every leg of the batch, or none: the response is `422 Unprocessable Entity` when any
+/// leg failed, the others being reported as rolled back.
+#[rocket::post("/<source_account_id>/batch-transfers", data = "<request>")]
+pub async fn send_money_batch(
+    source_account_id: u64,
+    request: Result<Json<BatchTransferRequest>, json::Error<'_>>,
+    send_money_batch_service: Inject<'_, dyn SendMoneyBatchUseCase>,
+    money_transfer_properties: Inject<'_, dyn MoneyTransferProperties>,
+) -> Result<Custom<Json<BatchTransferResponse>>, ApiError> {
+    let request = request.map_err(json_error)?;
+
+    let legs = request
+        .legs
+        .iter()
+        .map(|leg| (AccountId(leg.target_account_id), leg.money))
+        .collect();
+    let cmd = SendMoneyBatchCommand::try_new(
+        AccountId(source_account_id),
+        legs,
+        money_transfer_properties.command_ceiling(),
+    )?;
+
+    let outcome = send_money_batch_service.send_money_batch(cmd).await?;
+    let committed = outcome.is_committed();
+    let status = if committed {
+        Status::Ok
+    } else {
+        Status::UnprocessableEntity
+    };
+
+    Ok(Custom(
+        status,
+        Json(BatchTransferResponse {
+            source_account_id,
+            committed,
+            legs: outcome.into_legs().into_iter().map(Into::into).collect(),
+        }),
+    ))
+}
+
+#[rocket::post("/<source_account_id>/scheduled-transfers", data = "<request>")]
+pub async fn schedule_transfer(
+    source_account_id: u64,
+    request: Result<Json<ScheduleTransferRequest>, json::Error<'_>>,
+    schedule_transfer_service: Inject<'_, dyn ScheduleTransferUseCase>,
+    money_transfer_properties: Inject<'_, dyn MoneyTransferProperties>,
+) -> Result<Accepted<Json<ScheduledTransferResponse>>, ApiError> {
+    let request = request.map_err(json_error)?;
+
+    let cmd = ScheduleTransferCommand::try_new(
+        AccountId(source_account_id),
+        AccountId(request.target_account_id),
+        request.money,
+        request.execute_at,
+        money_transfer_properties.command_ceiling(),
+    )?;
+
+    let id = schedule_transfer_service.schedule_transfer(cmd).await?;
+
+    Ok(Accepted(Some(Json(ScheduledTransferResponse {
+        id: id.0,
+        source_account_id,
+        target_account_id: request.target_account_id,
+        amount: request.money.amount(),
+        currency: request.money.currency().code(),
+        execute_at: request.execute_at,
+        status: ScheduledTransferStatus::Pending.code(),
+    }))))
+}
+
+#[rocket::post("/", data = "<request>")]
+pub async fn open_account(
+    request: Result<Json<OpenAccountRequest>, json::Error<'_>>,
+    open_account_service: Inject<'_, dyn OpenAccountUseCase>,
+    create_account_service: Inject<'_, dyn CreateAccountUseCase>,
+) -> Result<Created<Json<AccountStatusResponse>>, ApiError> {
+    let request = request.map_err(json_error)?;
+    let currency = parse_currency(&request.currency)?;
+    let overdraft_limit = request
+        .overdraft_limit
+        .map(|overdraft_limit| Money::new(overdraft_limit, currency));
+
+    let account_id = match request.id {
+        Some(id) => {
+            let cmd = OpenAccountCommand::try_new(AccountId(id), currency, overdraft_limit)?;
+            open_account_service.open_account(cmd).await?;
+            AccountId(id)
+        }
+        None => {
+            let cmd = CreateAccountCommand::try_new(currency, overdraft_limit)?;
+            create_account_service.create_account(cmd).await?
+        }
+    };
+
+    Ok(
+        Created::new(format!("/accounts/{}", account_id.0)).body(Json(AccountStatusResponse {
+            id: account_id.0,
+            status: AccountStatus::Active.code(),
+        })),
+    )
+}
+
+#[rocket::post("/<account_id>/close")]
+pub async fn close_account(
+    account_id: u64,
+    close_account_service: Inject<'_, dyn CloseAccountUseCase>,
+) -> Result<Json<AccountStatusResponse>, ApiError> {
+    close_account_service
+        .close_account(AccountId(account_id))
+        .await?;
+
+    Ok(Json(AccountStatusResponse {
+        id: account_id,
+        status: AccountStatus::Closed.code(),
+    }))
+}
+
+fn json_error(err: json::Error<'_>) -> ApiError {
+    match err {
+        json::Error::Io(e) => ApiError::bad_request(e.to_string()),
+        json::Error::Parse(_, e) => ApiError::bad_request(e.to_string()),
+    }
+}
+
+/// Parse an ISO 4217 currency code.
+fn parse_currency(code: &str) -> Result<Currency, ApiError> {
+    Currency::from_code(code)
+        .ok_or_else(|| ApiError::bad_request(format!("Unsupported currency '{}'", code)))
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use chrono::TimeZone;
+    use rocket::{
+        http::{ContentType, Header, Status},
+        local::asynchronous::Client,
+        tokio,
+    };
+
+    use crate::{
+        adapter::input::rest,
+        application::port::input::{
+            BatchTransferOutcome, CloseAccountError, CreateAccountError, GetAccountBalanceError,
+            OpenAccountError, ScheduleTransferError, SendMoneyError,
+        },
+        domain::{
+            account::AccountError,
+            activity::tests::default_activity,
+            money::{tests::eur, MoneyError},
+            scheduled_transfer::ScheduledTransferId,
+        },
+        infrastructure::tests::{self, mock_module},
+    };
+
+    use super::*;
+
+    /// Sends money only when the source account is `1`, with at most `500` units, and rejects
+    /// the `reused` idempotency key.
+    pub struct MockSendMoneyUseCase;
+
+    #[rocket::async_trait]
+    impl SendMoneyUseCase for MockSendMoneyUseCase {
+        async fn send_money(&self, cmd: SendMoneyCommand) -> Result<(), SendMoneyError> {
+            if *cmd.source_account_id() != AccountId(1) {
+                return Err(SendMoneyError::AccountNotFound(*cmd.source_account_id()));
+            }
+
+            if let Some(key) = cmd.idempotency_key().filter(|key| key.0 == "reused") {
+                return Err(SendMoneyError::IdempotencyKeyReused(key.clone()));
+            }
+
+            if cmd.money().currency() != Currency::EUR {
+                return Err(AccountError::Money(MoneyError::CurrencyMismatch {
+                    expected: Currency::EUR,
+                    actual: cmd.money().currency(),
+                })
+                .into());
+            }
+
+            if *cmd.money() > eur(500) {
+                return Err(AccountError::InsufficientFunds {
+                    available: eur(500),
+                    requested: *cmd.money(),
+                }
+                .into());
+            }
+
+            Ok(())
+        }
+    }
+
+    /// Sends batches only from account `1`, failing legs to the unknown account `3`.
+    pub struct MockSendMoneyBatchUseCase;
+
+    #[rocket::async_trait]
+    impl SendMoneyBatchUseCase for MockSendMoneyBatchUseCase {
+        async fn send_money_batch(
+            &self,
+            cmd: SendMoneyBatchCommand,
+        ) -> Result<BatchTransferOutcome, SendMoneyError> {
+            if *cmd.source_account_id() != AccountId(1) {
+                return Err(SendMoneyError::AccountNotFound(*cmd.source_account_id()));
+            }
+
+            let failed = cmd
+                .legs()
+                .iter()
+                .any(|leg| *leg.target_account_id() == AccountId(3));
+            let legs = cmd
+                .legs()
+                .iter()
+                .map(|leg| {
+                    let status = match *leg.target_account_id() {
+                        AccountId(3) => LegStatus::Failed(SendMoneyError::AccountNotFound(
+                            *leg.target_account_id(),
+                        )),
+                        _ if failed => LegStatus::RolledBack,
+                        _ => LegStatus::Completed,
+                    };
+                    LegOutcome::new(*leg, status)
+                })
+                .collect();
+
+            Ok(BatchTransferOutcome::new(legs))
+        }
+    }
+
+    /// Knows only account `1`, with two activities in its window.
+    pub struct MockGetAccountBalanceQuery;
+
+    #[rocket::async_trait]
+    impl GetAccountBalanceQuery for MockGetAccountBalanceQuery {
+        async fn get_account_balance(
+            &self,
+            account_id: AccountId,
+            since: Option<DateTime<Utc>>,
+        ) -> Result<AccountBalance, GetAccountBalanceError> {
+            if account_id != AccountId(1) {
+                return Err(GetAccountBalanceError::AccountNotFound(account_id));
+            }
+
+            Ok(AccountBalance::new(
+                account_id,
+                since.unwrap_or_else(Utc::now),
+                eur(500),
+                eur(1500),
+                vec![
+                    default_activity().build().unwrap(),
+                    default_activity().build().unwrap(),
+                ],
+            )
+            .with_overdraft_limit(Some(eur(200))))
+        }
+    }
+
+    /// Knows only account `1`, so any other account can be opened.
+    pub struct MockOpenAccountUseCase;
+
+    #[rocket::async_trait]
+    impl OpenAccountUseCase for MockOpenAccountUseCase {
+        async fn open_account(&self, cmd: OpenAccountCommand) -> Result<(), OpenAccountError> {
+            if *cmd.account_id() == AccountId(1) {
+                return Err(OpenAccountError::AccountAlreadyExists(*cmd.account_id()));
+            }
+
+            Ok(())
+        }
+    }
+
+    /// Schedules every transfer as `5`.
+    pub struct MockScheduleTransferUseCase;
+
+    #[rocket::async_trait]
+    impl ScheduleTransferUseCase for MockScheduleTransferUseCase {
+        async fn schedule_transfer(
+            &self,
+            _cmd: ScheduleTransferCommand,
+        ) -> Result<ScheduledTransferId, ScheduleTransferError> {
+            Ok(ScheduledTransferId(5))
+        }
+    }
+
+    /// Always creates account `10`.
+    pub struct MockCreateAccountUseCase;
+
+    #[rocket::async_trait]
+    impl CreateAccountUseCase for MockCreateAccountUseCase {
+        async fn create_account(
+            &self,
+            _cmd: CreateAccountCommand,
+        ) -> Result<AccountId, CreateAccountError> {
+            Ok(AccountId(10))
+        }
+    }
+
+    /// Closes account `1`, whose balance is zero, but not account `2`.
+    pub struct MockCloseAccountUseCase;
+
+    #[rocket::async_trait]
+    impl CloseAccountUseCase for MockCloseAccountUseCase {
+        async fn close_account(&self, account_id: AccountId) -> Result<(), CloseAccountError> {
+            match account_id {
+                AccountId(1) => Ok(()),
+                AccountId(2) => Err(AccountError::NonZeroBalance(eur(10)).into()),
+                account_id => Err(CloseAccountError::AccountNotFound(account_id)),
+            }
+        }
+    }
+
+    async fn client() -> Client {
+        tests::setup();
+        let module = mock_module()
+            .await
+            .with_component_override::<dyn SendMoneyUseCase>(Box::new(MockSendMoneyUseCase))
+            .with_component_override::<dyn SendMoneyBatchUseCase>(Box::new(
+                MockSendMoneyBatchUseCase,
+            ))
+            .with_component_override::<dyn GetAccountBalanceQuery>(Box::new(
+                MockGetAccountBalanceQuery,
+            ))
+            .with_component_override::<dyn OpenAccountUseCase>(Box::new(MockOpenAccountUseCase))
+            .with_component_override::<dyn CreateAccountUseCase>(Box::new(MockCreateAccountUseCase))
+            .with_component_override::<dyn ScheduleTransferUseCase>(Box::new(
+                MockScheduleTransferUseCase,
+            ))
+            .with_component_override::<dyn CloseAccountUseCase>(Box::new(MockCloseAccountUseCase))
+            .build();
+
+        let rocket =
+            rocket::build()
+                .manage(Box::new(module))
+                .attach(rocket::fairing::AdHoc::try_on_ignite(
+                    "REST Adapter",
+                    rest::configure_rest,
+                ));
+
+        Client::tracked(rocket).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_sends_money() -> Result<()> {
+        let client = client().await;
+
+        let response = client
+            .post("/accounts/1/transfers")
+            .header(ContentType::JSON)
+            .body(r#"{ "target_account_id": 2, "money": "3.00 EUR" }"#)
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_sends_money_with_idempotency_keys() -> Result<()> {
+        let client = client().await;
+
+        let response = client
+            .post("/accounts/1/transfers")
+            .header(ContentType::JSON)
+            .header(Header::new("Idempotency-Key", "transfer-1"))
+            .body(r#"{ "target_account_id": 2, "money": "3.00 EUR" }"#)
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_rejects_reused_idempotency_keys() -> Result<()> {
+        let client = client().await;
+
+        let response = client
+            .post("/accounts/1/transfers")
+            .header(ContentType::JSON)
+            .header(Header::new("Idempotency-Key", "reused"))
+            .body(r#"{ "target_account_id": 2, "money": "3.00 EUR" }"#)
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+        assert!(response
+            .into_string()
+            .await
+            .unwrap()
+            .contains("idempotency_key_reused"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_rejects_insufficient_funds() -> Result<()> {
+        let client = client().await;
+
+        let response = client
+            .post("/accounts/1/transfers")
+            .header(ContentType::JSON)
+            .body(r#"{ "target_account_id": 2, "money": "5.01 EUR" }"#)
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+        assert!(response
+            .into_string()
+            .await
+            .unwrap()
+            .contains("insufficient_funds"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_rejects_currency_mismatches() -> Result<()> {
+        let client = client().await;
+
+        let response = client
+            .post("/accounts/1/transfers")
+            .header(ContentType::JSON)
+            .body(r#"{ "target_account_id": 2, "money": "3.00 USD" }"#)
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+        assert!(response
+            .into_string()
+            .await
+            .unwrap()
+            .contains("currency_mismatch"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_rejects_unsupported_currencies() -> Result<()> {
+        let client = client().await;
+
+        let response = client
+            .post("/accounts/1/transfers")
+            .header(ContentType::JSON)
+            .body(r#"{ "target_account_id": 2, "money": "3.00 XXX" }"#)
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_rejects_money_without_currency() -> Result<()> {
+        let client = client().await;
+
+        let response = client
+            .post("/accounts/1/transfers")
+            .header(ContentType::JSON)
+            .body(r#"{ "target_account_id": 2, "money": { "amount": 300 } }"#)
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_rejects_unknown_accounts() -> Result<()> {
+        let client = client().await;
+
+        let response = client
+            .post("/accounts/99/transfers")
+            .header(ContentType::JSON)
+            .body(r#"{ "target_account_id": 2, "money": "3.00 EUR" }"#)
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NotFound);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_rejects_malformed_requests() -> Result<()> {
+        let client = client().await;
+
+        let response = client
+            .post("/accounts/1/transfers")
+            .header(ContentType::JSON)
+            .body(r#"{ "money": "3.00 EUR" }"#)
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_rejects_invalid_transfers() -> Result<()> {
+        let client = client().await;
+
+        let response = client
+            .post("/accounts/1/transfers")
+            .header(ContentType::JSON)
+            .body(r#"{ "target_account_id": 1, "money": "0.00 EUR" }"#)
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+        let body = response.into_string().await.unwrap();
+        assert!(body.contains(r#""field":"money""#));
+        assert!(body.contains(r#""field":"target_account_id""#));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_sends_money_batches() -> Result<()> {
+        let client = client().await;
+
+        let response = client
+            .post("/accounts/1/batch-transfers")
+            .header(ContentType::JSON)
+            .body(
+                r#"{ "legs": [
+                    { "target_account_id": 2, "money": "3.00 EUR" },
+                    { "target_account_id": 4, "money": { "amount": 200, "currency": "EUR" } }
+                ] }"#,
+            )
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let body = response.into_string().await.unwrap();
+        assert!(body.contains(r#""committed":true"#));
+        assert_eq!(body.matches(r#""status":"completed""#).count(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_reports_failed_batch_legs() -> Result<()> {
+        let client = client().await;
+
+        let response = client
+            .post("/accounts/1/batch-transfers")
+            .header(ContentType::JSON)
+            .body(
+                r#"{ "legs": [
+                    { "target_account_id": 2, "money": "3.00 EUR" },
+                    { "target_account_id": 3, "money": "2.00 EUR" }
+                ] }"#,
+            )
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+        let body = response.into_string().await.unwrap();
+        assert!(body.contains(r#""committed":false"#));
+        assert!(body.contains(r#""status":"rolled_back""#));
+        assert!(body.contains(r#""status":"failed""#));
+        assert!(body.contains(r#""error":"not_found""#));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_rejects_empty_batches() -> Result<()> {
+        let client = client().await;
+
+        let response = client
+            .post("/accounts/1/batch-transfers")
+            .header(ContentType::JSON)
+            .body(r#"{ "legs": [] }"#)
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_gets_account() -> Result<()> {
+        let client = client().await;
+
+        let response = client.get("/accounts/1").dispatch().await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let body = response.into_string().await.unwrap();
+        assert!(body.contains(r#""baseline_balance":500"#));
+        assert!(body.contains(r#""balance":1500"#));
+        assert!(body.contains(r#""currency":"EUR""#));
+        assert!(body.contains(r#""overdraft_limit":200"#));
+        assert!(body.contains(r#""status":"active""#));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_gets_account_activities_since() -> Result<()> {
+        let client = client().await;
+
+        let response = client
+            .get("/accounts/1/activities?since=2019-08-03T00:00:00Z")
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let body = response.into_string().await.unwrap();
+        let since = Utc.ymd(2019, 8, 3).and_hms(0, 0, 0);
+        assert!(body.contains(&format!(
+            r#""since":"{}""#,
+            since.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+        )));
+        assert_eq!(body.matches(r#""owner_account_id""#).count(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_rejects_invalid_since_dates() -> Result<()> {
+        let client = client().await;
+
+        let response = client
+            .get("/accounts/1/activities?since=yesterday")
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_does_not_get_unknown_accounts() -> Result<()> {
+        let client = client().await;
+
+        let response = client.get("/accounts/99").dispatch().await;
+
+        assert_eq!(response.status(), Status::NotFound);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_opens_accounts() -> Result<()> {
+        let client = client().await;
+
+        let response = client
+            .post("/accounts")
+            .header(ContentType::JSON)
+            .body(r#"{ "id": 3, "currency": "USD", "overdraft_limit": 10000 }"#)
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Created);
+        assert_eq!(response.headers().get_one("Location"), Some("/accounts/3"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_opens_accounts_with_generated_ids() -> Result<()> {
+        let client = client().await;
+
+        let response = client
+            .post("/accounts")
+            .header(ContentType::JSON)
+            .body(r#"{ "currency": "EUR" }"#)
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Created);
+        assert_eq!(response.headers().get_one("Location"), Some("/accounts/10"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_rejects_opening_existing_accounts() -> Result<()> {
+        let client = client().await;
+
+        let response = client
+            .post("/accounts")
+            .header(ContentType::JSON)
+            .body(r#"{ "id": 1, "currency": "EUR" }"#)
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Conflict);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_closes_accounts() -> Result<()> {
+        let client = client().await;
+
+        let response = client.post("/accounts/1/close").dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+        assert!(response
+            .into_string()
+            .await
+            .unwrap()
+            .contains(r#""status":"closed""#));
+
+        let response = client.post("/accounts/2/close").dispatch().await;
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+
+        let response = client.post("/accounts/3/close").dispatch().await;
+        assert_eq!(response.status(), Status::NotFound);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_schedules_transfers() -> Result<()> {
+        let client = client().await;
+        let execute_at = Utc::now() + chrono::Duration::days(1);
+
+        let response = client
+            .post("/accounts/1/scheduled-transfers")
+            .header(ContentType::JSON)
+            .body(format!(
+                r#"{{ "target_account_id": 2, "money": "3.00 EUR", "execute_at": "{}" }}"#,
+                execute_at.to_rfc3339()
+            ))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Accepted);
+        let body = response.into_string().await.unwrap();
+        assert!(body.contains(r#""id":5"#));
+        assert!(body.contains(r#""status":"pending""#));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_rejects_transfers_scheduled_in_the_past() -> Result<()> {
+        let client = client().await;
+
+        let response = client
+            .post("/accounts/1/scheduled-transfers")
+            .header(ContentType::JSON)
+            .body(r#"{ "target_account_id": 2, "money": "3.00 EUR", "execute_at": "2019-08-03T00:00:00Z" }"#)
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+        assert!(response.into_string().await.unwrap().contains("execute_at"));
+        Ok(())
+    }
+}
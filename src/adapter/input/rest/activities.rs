@@ -0,0 +1,159 @@
+use rocket::serde::{json::Json, Serialize};
+
+use crate::{
+    application::port::input::ReverseTransferUseCase, domain::activity::ActivityId,
+    infrastructure::container::Inject,
+};
+
+use super::{accounts::ActivityResponse, error::ApiError};
+
+#[derive(Debug, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ReversalResponse {
+    reversed_activity_id: u64,
+    /// Compensating activities of the source and target accounts.
+    activities: Vec<ActivityResponse>,
+}
+
+#[rocket::post("/<activity_id>/reversal")]
+pub async fn reverse_transfer(
+    activity_id: u64,
+    reverse_transfer_service: Inject<'_, dyn ReverseTransferUseCase>,
+) -> Result<Json<ReversalResponse>, ApiError> {
+    let activities = reverse_transfer_service
+        .reverse_transfer(ActivityId(activity_id))
+        .await?;
+
+    Ok(Json(ReversalResponse {
+        reversed_activity_id: activity_id,
+        activities: activities.iter().map(Into::into).collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use rocket::{http::Status, local::asynchronous::Client, tokio};
+
+    use crate::{
+        adapter::input::rest,
+        application::port::input::ReverseTransferError,
+        domain::{
+            account::AccountId,
+            activity::{tests::default_activity, Activity},
+            money::tests::eur,
+        },
+        infrastructure::tests::{self, mock_module},
+    };
+
+    use super::*;
+
+    /// Reverses activity `1` only, activity `2` having been reversed already and activity `4`
+    /// having no transfer id.
+    pub struct MockReverseTransferUseCase;
+
+    #[rocket::async_trait]
+    impl ReverseTransferUseCase for MockReverseTransferUseCase {
+        async fn reverse_transfer(
+            &self,
+            activity_id: ActivityId,
+        ) -> Result<Vec<Activity>, ReverseTransferError> {
+            match activity_id {
+                ActivityId(1) => Ok([AccountId(2), AccountId(1)]
+                    .iter()
+                    .zip(3..)
+                    .map(|(owner, id)| {
+                        default_activity()
+                            .id(Some(ActivityId(id)))
+                            .owner_account_id(*owner)
+                            .source_account_id(AccountId(2))
+                            .target_account_id(AccountId(1))
+                            .money(eur(500))
+                            .reverses_activity_id(Some(activity_id))
+                            .build()
+                            .unwrap()
+                    })
+                    .collect()),
+                ActivityId(2) => Err(ReverseTransferError::AlreadyReversed {
+                    original: activity_id,
+                    reversal: ActivityId(3),
+                }),
+                ActivityId(4) => Err(ReverseTransferError::UnlinkedActivity(activity_id)),
+                id => Err(ReverseTransferError::ActivityNotFound(id)),
+            }
+        }
+    }
+
+    async fn client() -> Client {
+        tests::setup();
+        let module = mock_module()
+            .await
+            .with_component_override::<dyn ReverseTransferUseCase>(Box::new(
+                MockReverseTransferUseCase,
+            ))
+            .build();
+
+        let rocket =
+            rocket::build()
+                .manage(Box::new(module))
+                .attach(rocket::fairing::AdHoc::try_on_ignite(
+                    "REST Adapter",
+                    rest::configure_rest,
+                ));
+
+        Client::tracked(rocket).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_reverses_transfers() -> Result<()> {
+        let client = client().await;
+
+        let response = client.post("/activities/1/reversal").dispatch().await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let body = response.into_string().await.unwrap();
+        assert!(body.contains(r#""reversed_activity_id":1"#));
+        assert_eq!(body.matches(r#""reverses_activity_id":1"#).count(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_rejects_reversed_transfers() -> Result<()> {
+        let client = client().await;
+
+        let response = client.post("/activities/2/reversal").dispatch().await;
+
+        assert_eq!(response.status(), Status::Conflict);
+        assert!(response
+            .into_string()
+            .await
+            .unwrap()
+            .contains("already_reversed"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_rejects_unlinked_activities() -> Result<()> {
+        let client = client().await;
+
+        let response = client.post("/activities/4/reversal").dispatch().await;
+
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+        assert!(response
+            .into_string()
+            .await
+            .unwrap()
+            .contains("unlinked_activity"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_rejects_unknown_activities() -> Result<()> {
+        let client = client().await;
+
+        let response = client.post("/activities/99/reversal").dispatch().await;
+
+        assert_eq!(response.status(), Status::NotFound);
+        Ok(())
+    }
+}
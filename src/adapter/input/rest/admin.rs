@@ -0,0 +1,143 @@
+use rocket::serde::{json::Json, Serialize};
+
+use crate::{
+    application::port::input::LedgerAuditUseCase,
+    domain::ledger::{LedgerAudit, LedgerMismatch},
+    infrastructure::container::Inject,
+};
+
+use super::{accounts::ActivityResponse, error::ApiError};
+
+#[derive(Debug, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct LedgerAuditResponse {
+    consistent: bool,
+    activity_count: usize,
+    pair_count: usize,
+    /// Activities without a counterpart in the other account of their transfer.
+    orphans: Vec<ActivityResponse>,
+    mismatches: Vec<LedgerMismatchResponse>,
+    /// Non-zero sums of the ledger, one per currency.
+    imbalances: Vec<ImbalanceResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct LedgerMismatchResponse {
+    withdrawal: ActivityResponse,
+    deposit: ActivityResponse,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ImbalanceResponse {
+    amount: i64,
+    currency: &'static str,
+}
+
+impl From<&LedgerMismatch> for LedgerMismatchResponse {
+    fn from(mismatch: &LedgerMismatch) -> Self {
+        Self {
+            withdrawal: mismatch.withdrawal().into(),
+            deposit: mismatch.deposit().into(),
+        }
+    }
+}
+
+impl From<&LedgerAudit> for LedgerAuditResponse {
+    fn from(audit: &LedgerAudit) -> Self {
+        Self {
+            consistent: audit.is_consistent(),
+            activity_count: audit.activity_count(),
+            pair_count: audit.pair_count(),
+            orphans: audit.orphans().iter().map(Into::into).collect(),
+            mismatches: audit.mismatches().iter().map(Into::into).collect(),
+            imbalances: audit
+                .imbalances()
+                .iter()
+                .map(|sum| ImbalanceResponse {
+                    amount: sum.amount(),
+                    currency: sum.currency().code(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[rocket::get("/ledger-audit")]
+pub async fn audit_ledger(
+    ledger_audit_service: Inject<'_, dyn LedgerAuditUseCase>,
+) -> Result<Json<LedgerAuditResponse>, ApiError> {
+    let audit = ledger_audit_service.audit_ledger().await?;
+
+    Ok(Json((&audit).into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use rocket::{http::Status, local::asynchronous::Client, tokio};
+
+    use crate::{
+        adapter::input::rest,
+        application::port::input::LedgerAuditError,
+        domain::{
+            account::AccountId,
+            activity::{tests::default_activity, ActivityId},
+            money::tests::eur,
+        },
+        infrastructure::tests::{self, mock_module},
+    };
+
+    use super::*;
+
+    /// Finds a single orphan withdrawal in the ledger.
+    pub struct MockLedgerAuditUseCase;
+
+    #[rocket::async_trait]
+    impl LedgerAuditUseCase for MockLedgerAuditUseCase {
+        async fn audit_ledger(&self) -> Result<LedgerAudit, LedgerAuditError> {
+            let orphan = default_activity()
+                .id(Some(ActivityId(7)))
+                .source_account_id(AccountId(1))
+                .target_account_id(AccountId(2))
+                .money(eur(500))
+                .build()
+                .unwrap();
+
+            Ok(LedgerAudit::of(vec![orphan])?)
+        }
+    }
+
+    async fn client() -> Client {
+        tests::setup();
+        let module = mock_module()
+            .await
+            .with_component_override::<dyn LedgerAuditUseCase>(Box::new(MockLedgerAuditUseCase))
+            .build();
+
+        let rocket =
+            rocket::build()
+                .manage(Box::new(module))
+                .attach(rocket::fairing::AdHoc::try_on_ignite(
+                    "REST Adapter",
+                    rest::configure_rest,
+                ));
+
+        Client::tracked(rocket).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_audits_the_ledger() -> Result<()> {
+        let client = client().await;
+
+        let response = client.get("/admin/ledger-audit").dispatch().await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let body = response.into_string().await.unwrap();
+        assert!(body.contains(r#""consistent":false"#));
+        assert!(body.contains(r#""orphans":[{"id":7"#));
+        assert!(body.contains(r#""imbalances":[{"amount":-500,"currency":"EUR"}]"#));
+        Ok(())
+    }
+}
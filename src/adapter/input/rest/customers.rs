@@ -0,0 +1,128 @@
+use rocket::serde::{json::Json, Serialize};
+
+use crate::{
+    application::port::input::{CustomerAccounts, GetCustomerAccountsQuery},
+    domain::customer::CustomerId,
+    infrastructure::container::Inject,
+};
+
+use super::{accounts::AccountResponse, error::ApiError};
+
+#[derive(Debug, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct CustomerAccountsResponse {
+    id: u64,
+    name: String,
+    accounts: Vec<AccountResponse>,
+}
+
+impl From<&CustomerAccounts> for CustomerAccountsResponse {
+    fn from(customer_accounts: &CustomerAccounts) -> Self {
+        Self {
+            id: customer_accounts.customer().id().0,
+            name: customer_accounts.customer().name().to_owned(),
+            accounts: customer_accounts
+                .accounts()
+                .iter()
+                .map(Into::into)
+                .collect(),
+        }
+    }
+}
+
+#[rocket::get("/<customer_id>/accounts")]
+pub async fn get_customer_accounts(
+    customer_id: u64,
+    get_customer_accounts_query: Inject<'_, dyn GetCustomerAccountsQuery>,
+) -> Result<Json<CustomerAccountsResponse>, ApiError> {
+    let customer_accounts = get_customer_accounts_query
+        .get_customer_accounts(CustomerId(customer_id))
+        .await?;
+
+    Ok(Json((&customer_accounts).into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use chrono::Utc;
+    use rocket::{http::Status, local::asynchronous::Client, tokio};
+
+    use crate::{
+        adapter::input::rest,
+        application::port::input::{AccountBalance, GetCustomerAccountsError},
+        domain::{account::AccountId, customer::Customer, money::tests::eur},
+        infrastructure::tests::{self, mock_module},
+    };
+
+    use super::*;
+
+    /// Knows only customer `1`, owning accounts `1` and `2`.
+    pub struct MockGetCustomerAccountsQuery;
+
+    #[rocket::async_trait]
+    impl GetCustomerAccountsQuery for MockGetCustomerAccountsQuery {
+        async fn get_customer_accounts(
+            &self,
+            customer_id: CustomerId,
+        ) -> Result<CustomerAccounts, GetCustomerAccountsError> {
+            if customer_id != CustomerId(1) {
+                return Err(GetCustomerAccountsError::CustomerNotFound(customer_id));
+            }
+
+            let account_ids = vec![AccountId(1), AccountId(2)];
+            let accounts = account_ids
+                .iter()
+                .map(|id| AccountBalance::new(*id, Utc::now(), eur(0), eur(100), vec![]))
+                .collect();
+
+            Ok(CustomerAccounts::new(
+                Customer::new(customer_id, "Ada Lovelace".into(), account_ids),
+                accounts,
+            ))
+        }
+    }
+
+    async fn client() -> Client {
+        tests::setup();
+        let module = mock_module()
+            .await
+            .with_component_override::<dyn GetCustomerAccountsQuery>(Box::new(
+                MockGetCustomerAccountsQuery,
+            ))
+            .build();
+
+        let rocket =
+            rocket::build()
+                .manage(Box::new(module))
+                .attach(rocket::fairing::AdHoc::try_on_ignite(
+                    "REST Adapter",
+                    rest::configure_rest,
+                ));
+
+        Client::tracked(rocket).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_gets_customer_accounts() -> Result<()> {
+        let client = client().await;
+
+        let response = client.get("/customers/1/accounts").dispatch().await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let body = response.into_string().await.unwrap();
+        assert!(body.contains(r#""name":"Ada Lovelace""#));
+        assert_eq!(body.matches(r#""balance":100"#).count(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_rejects_unknown_customers() -> Result<()> {
+        let client = client().await;
+
+        let response = client.get("/customers/2/accounts").dispatch().await;
+
+        assert_eq!(response.status(), Status::NotFound);
+        Ok(())
+    }
+}
@@ -0,0 +1,282 @@
+use std::fmt::Debug;
+
+use rocket::{
+    http::Status,
+    response::{self, Responder},
+    serde::{json::Json, Serialize},
+    Request,
+};
+
+use crate::{
+    application::port::{
+        input::{
+            CloseAccountError, CreateAccountError, GetAccountBalanceError,
+            GetCustomerAccountsError, LedgerAuditError, OpenAccountError, ReverseTransferError,
+            ScheduleTransferError, SendMoneyError, ValidationError,
+        },
+        output::{ExchangeRateError, PersistenceError},
+    },
+    domain::{account::AccountError, money::MoneyError},
+};
+
+/// JSON body returned to REST clients whenever a request cannot be fulfilled.
+#[derive(Debug, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ErrorBody {
+    error: &'static str,
+    message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    violations: Vec<ViolationBody>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ViolationBody {
+    field: &'static str,
+    message: String,
+}
+
+#[derive(Debug)]
+pub struct ApiError {
+    status: Status,
+    body: ErrorBody,
+}
+
+impl ApiError {
+    pub fn new(status: Status, error: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: ErrorBody {
+                error,
+                message: message.into(),
+                violations: vec![],
+            },
+        }
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(Status::BadRequest, "bad_request", message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(Status::NotFound, "not_found", message)
+    }
+
+    /// Consume the error, returning its body to be nested in another response.
+    pub fn into_body(self) -> ErrorBody {
+        self.body
+    }
+
+    /// Log the unexpected `err` and hide its details from the client.
+    pub fn internal(err: impl Debug) -> Self {
+        log::error!("Unexpected error: {:?}", err);
+        Self::new(
+            Status::InternalServerError,
+            "internal_error",
+            "Unexpected error",
+        )
+    }
+}
+
+impl<'r> Responder<'r, 'static> for ApiError {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        (self.status, Json(self.body)).respond_to(req)
+    }
+}
+
+impl From<ValidationError> for ApiError {
+    fn from(err: ValidationError) -> Self {
+        let mut api_error = Self::new(Status::BadRequest, "validation_failed", err.to_string());
+        api_error.body.violations = err
+            .violations()
+            .iter()
+            .map(|v| ViolationBody {
+                field: v.field(),
+                message: v.message().to_owned(),
+            })
+            .collect();
+
+        api_error
+    }
+}
+
+impl From<PersistenceError> for ApiError {
+    fn from(err: PersistenceError) -> Self {
+        match err {
+            PersistenceError::AccountNotFound(_)
+            | PersistenceError::ActivityNotFound(_)
+            | PersistenceError::CustomerNotFound(_)
+            | PersistenceError::TransferNotFound(_) => Self::not_found(err.to_string()),
+            err => Self::internal(err),
+        }
+    }
+}
+
+impl From<MoneyError> for ApiError {
+    fn from(err: MoneyError) -> Self {
+        match err {
+            MoneyError::CurrencyMismatch { .. } => Self::new(
+                Status::UnprocessableEntity,
+                "currency_mismatch",
+                err.to_string(),
+            ),
+            MoneyError::Overflow(_) => {
+                Self::new(Status::UnprocessableEntity, "overflow", err.to_string())
+            }
+            MoneyError::EmptySum => Self::internal(err),
+        }
+    }
+}
+
+impl From<SendMoneyError> for ApiError {
+    fn from(err: SendMoneyError) -> Self {
+        match err {
+            SendMoneyError::AccountNotFound(_) => Self::not_found(err.to_string()),
+            SendMoneyError::ThresholdExceeded { .. } => Self::new(
+                Status::UnprocessableEntity,
+                "threshold_exceeded",
+                err.to_string(),
+            ),
+            SendMoneyError::IdempotencyKeyReused(_) => Self::new(
+                Status::UnprocessableEntity,
+                "idempotency_key_reused",
+                err.to_string(),
+            ),
+            SendMoneyError::Account(AccountError::InsufficientFunds { .. }) => Self::new(
+                Status::UnprocessableEntity,
+                "insufficient_funds",
+                err.to_string(),
+            ),
+            SendMoneyError::Account(AccountError::NotActive(_)) => Self::new(
+                Status::UnprocessableEntity,
+                "account_not_active",
+                err.to_string(),
+            ),
+            SendMoneyError::Account(AccountError::Money(err)) | SendMoneyError::Money(err) => {
+                err.into()
+            }
+            SendMoneyError::ExchangeRate(ExchangeRateError::Unavailable { .. }) => Self::new(
+                Status::UnprocessableEntity,
+                "exchange_rate_unavailable",
+                err.to_string(),
+            ),
+            SendMoneyError::Account(err) => Self::internal(err),
+            SendMoneyError::Persistence(err) => err.into(),
+        }
+    }
+}
+
+impl From<GetAccountBalanceError> for ApiError {
+    fn from(err: GetAccountBalanceError) -> Self {
+        match err {
+            GetAccountBalanceError::AccountNotFound(_) => Self::not_found(err.to_string()),
+            GetAccountBalanceError::Account(err) => Self::internal(err),
+            GetAccountBalanceError::Persistence(err) => err.into(),
+        }
+    }
+}
+
+impl From<GetCustomerAccountsError> for ApiError {
+    fn from(err: GetCustomerAccountsError) -> Self {
+        match err {
+            GetCustomerAccountsError::CustomerNotFound(_)
+            | GetCustomerAccountsError::AccountNotFound(_) => Self::not_found(err.to_string()),
+            GetCustomerAccountsError::Account(err) => Self::internal(err),
+            GetCustomerAccountsError::Persistence(err) => err.into(),
+        }
+    }
+}
+
+impl From<OpenAccountError> for ApiError {
+    fn from(err: OpenAccountError) -> Self {
+        match err {
+            OpenAccountError::AccountAlreadyExists(_) => {
+                Self::new(Status::Conflict, "account_already_exists", err.to_string())
+            }
+            OpenAccountError::Persistence(err) => err.into(),
+        }
+    }
+}
+
+impl From<CreateAccountError> for ApiError {
+    fn from(err: CreateAccountError) -> Self {
+        match err {
+            CreateAccountError::AccountAlreadyExists(_) => {
+                Self::new(Status::Conflict, "account_already_exists", err.to_string())
+            }
+            CreateAccountError::Persistence(err) => err.into(),
+        }
+    }
+}
+
+impl From<ScheduleTransferError> for ApiError {
+    fn from(err: ScheduleTransferError) -> Self {
+        match err {
+            ScheduleTransferError::AccountNotFound(_) => Self::not_found(err.to_string()),
+            ScheduleTransferError::Persistence(err) => err.into(),
+        }
+    }
+}
+
+impl From<ReverseTransferError> for ApiError {
+    fn from(err: ReverseTransferError) -> Self {
+        match err {
+            ReverseTransferError::ActivityNotFound(_) => Self::not_found(err.to_string()),
+            ReverseTransferError::AlreadyReversed { .. } => {
+                Self::new(Status::Conflict, "already_reversed", err.to_string())
+            }
+            ReverseTransferError::ReversalNotReversible(_) => Self::new(
+                Status::UnprocessableEntity,
+                "reversal_not_reversible",
+                err.to_string(),
+            ),
+            ReverseTransferError::UnlinkedActivity(_) => Self::new(
+                Status::UnprocessableEntity,
+                "unlinked_activity",
+                err.to_string(),
+            ),
+            ReverseTransferError::Account(AccountError::InsufficientFunds { .. }) => Self::new(
+                Status::UnprocessableEntity,
+                "insufficient_funds",
+                err.to_string(),
+            ),
+            ReverseTransferError::Account(AccountError::NotActive(_)) => Self::new(
+                Status::UnprocessableEntity,
+                "account_not_active",
+                err.to_string(),
+            ),
+            ReverseTransferError::Account(AccountError::Money(err)) => err.into(),
+            ReverseTransferError::Account(err) => Self::internal(err),
+            ReverseTransferError::Persistence(err) => err.into(),
+        }
+    }
+}
+
+impl From<CloseAccountError> for ApiError {
+    fn from(err: CloseAccountError) -> Self {
+        match err {
+            CloseAccountError::AccountNotFound(_) => Self::not_found(err.to_string()),
+            CloseAccountError::Account(AccountError::NonZeroBalance(_)) => Self::new(
+                Status::UnprocessableEntity,
+                "non_zero_balance",
+                err.to_string(),
+            ),
+            CloseAccountError::Account(AccountError::InvalidStatusTransition { .. }) => Self::new(
+                Status::Conflict,
+                "invalid_status_transition",
+                err.to_string(),
+            ),
+            CloseAccountError::Account(err) => Self::internal(err),
+            CloseAccountError::Persistence(err) => err.into(),
+        }
+    }
+}
+
+impl From<LedgerAuditError> for ApiError {
+    fn from(err: LedgerAuditError) -> Self {
+        match err {
+            LedgerAuditError::Money(err) => Self::internal(err),
+            LedgerAuditError::Persistence(err) => err.into(),
+        }
+    }
+}
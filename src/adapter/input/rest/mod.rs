@@ -1,11 +1,34 @@
+mod accounts;
+mod activities;
+mod admin;
 mod api;
+mod customers;
+mod error;
 
 use rocket::{fairing, Build, Rocket};
 
 pub async fn configure_rest(rocket: Rocket<Build>) -> fairing::Result {
     let rocket = rocket
         .mount("/hello", rocket::routes![api::world])
-        .mount("/", rocket::routes![api::ping]);
+        .mount("/", rocket::routes![api::ping])
+        .mount(
+            "/accounts",
+            rocket::routes![
+                accounts::get_account,
+                accounts::get_account_activities,
+                accounts::send_money,
+                accounts::send_money_batch,
+                accounts::schedule_transfer,
+                accounts::open_account,
+                accounts::close_account
+            ],
+        )
+        .mount("/activities", rocket::routes![activities::reverse_transfer])
+        .mount("/admin", rocket::routes![admin::audit_ledger])
+        .mount(
+            "/customers",
+            rocket::routes![customers::get_customer_accounts],
+        );
 
     Ok(rocket)
 }
@@ -0,0 +1,151 @@
+use std::{collections::HashMap, sync::Arc};
+
+use parking_lot::Mutex;
+use rocket::tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+use sqlx::PgConnection;
+
+use crate::{
+    application::port::output::{AccountLock, PersistenceError},
+    domain::account::AccountId,
+};
+
+/// Locks of every account, shared by the `InMemoryAccountLock`s of the running process.
+#[derive(Default)]
+pub struct InMemoryAccountLocks {
+    locks: Mutex<HashMap<AccountId, Arc<AsyncMutex<()>>>>,
+}
+
+impl InMemoryAccountLocks {
+    /// Get an `AccountLock` holding accounts until it is dropped.
+    pub fn holder(self: &Arc<Self>) -> InMemoryAccountLock {
+        InMemoryAccountLock {
+            locks: self.clone(),
+            guards: HashMap::new(),
+        }
+    }
+}
+
+/// `AccountLock` scoped to the running process, only safe when running a single instance.
+pub struct InMemoryAccountLock {
+    locks: Arc<InMemoryAccountLocks>,
+    guards: HashMap<AccountId, OwnedMutexGuard<()>>,
+}
+
+#[rocket::async_trait]
+impl AccountLock for InMemoryAccountLock {
+    async fn lock_account(&mut self, account_id: AccountId) -> Result<(), PersistenceError> {
+        if self.guards.contains_key(&account_id) {
+            return Ok(());
+        }
+
+        let lock = self
+            .locks
+            .locks
+            .lock()
+            .entry(account_id)
+            .or_default()
+            .clone();
+        let guard = lock.lock_owned().await;
+        self.guards.insert(account_id, guard);
+
+        Ok(())
+    }
+
+    async fn release_account(&mut self, account_id: AccountId) -> Result<(), PersistenceError> {
+        self.guards.remove(&account_id);
+
+        Ok(())
+    }
+}
+
+/// `AccountLock` backed by PostgreSQL transaction-level advisory locks, shared by every instance
+/// connected to the same database.
+///
+/// Locks are bound to the transaction of `conn`, and released by its commit or rollback only.
+pub struct PostgresAccountLock<'c> {
+    conn: &'c mut PgConnection,
+}
+
+impl<'c> PostgresAccountLock<'c> {
+    pub fn new(conn: &'c mut PgConnection) -> Self {
+        Self { conn }
+    }
+}
+
+#[rocket::async_trait]
+impl AccountLock for PostgresAccountLock<'_> {
+    async fn lock_account(&mut self, account_id: AccountId) -> Result<(), PersistenceError> {
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(account_id.0 as i64)
+            .execute(&mut *self.conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn release_account(&mut self, _account_id: AccountId) -> Result<(), PersistenceError> {
+        // Transaction-level advisory locks cannot be released before the transaction ends
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicBool, Ordering},
+        time::Duration,
+    };
+
+    use anyhow::Result;
+    use rocket::tokio::{self, time::timeout};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn in_memory_lock_serializes_access() -> Result<()> {
+        // Given
+        let locks = Arc::new(InMemoryAccountLocks::default());
+        let mut holder = locks.holder();
+        holder.lock_account(AccountId(1)).await?;
+
+        // Expect another account can still be locked
+        let mut other = locks.holder();
+        timeout(Duration::from_secs(1), other.lock_account(AccountId(2))).await??;
+
+        // When the same account is locked by another holder
+        let acquired = Arc::new(AtomicBool::new(false));
+        let contender = tokio::spawn({
+            let mut contender = locks.holder();
+            let acquired = acquired.clone();
+            async move {
+                contender.lock_account(AccountId(1)).await?;
+                acquired.store(true, Ordering::SeqCst);
+                contender.release_account(AccountId(1)).await
+            }
+        });
+
+        // Expect it waits until the first one released it
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!acquired.load(Ordering::SeqCst));
+        holder.release_account(AccountId(1)).await?;
+        timeout(Duration::from_secs(1), contender).await???;
+        assert!(acquired.load(Ordering::SeqCst));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn in_memory_lock_is_released_when_dropped() -> Result<()> {
+        // Given
+        let locks = Arc::new(InMemoryAccountLocks::default());
+        let mut holder = locks.holder();
+        holder.lock_account(AccountId(1)).await?;
+
+        // When
+        drop(holder);
+
+        // Expect
+        let mut other = locks.holder();
+        timeout(Duration::from_secs(1), other.lock_account(AccountId(1))).await??;
+        Ok(())
+    }
+}
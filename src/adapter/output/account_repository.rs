@@ -1,16 +1,16 @@
 use std::{convert::TryInto, sync::Arc};
 
-use anyhow::Result;
 use chrono::{DateTime, Utc};
-use num_traits::ToPrimitive;
-use sqlx::types::BigDecimal;
+use sqlx::{types::BigDecimal, PgConnection};
 
+use super::money_from_minor_units;
 use crate::{
-    application::port::output::LoadAccountPort,
+    application::port::output::{LoadAccountPort, PersistenceError},
     domain::{
-        account::{Account, AccountBuilder, AccountId},
-        activity::{Activity, ActivityBuilder, ActivityId, ActivityWindow},
-        money::Money,
+        account::{Account, AccountBuilder, AccountId, AccountStatus},
+        activity::{Activity, ActivityBuilder, ActivityId, ActivityWindow, TransferId},
+        customer::CustomerId,
+        money::{Currency, Money},
     },
     infrastructure::db::DataSource,
 };
@@ -28,62 +28,105 @@ impl LoadAccountPort for AccountRepository {
         &self,
         account_id: AccountId,
         baseline_date: DateTime<Utc>,
-    ) -> Result<Account> {
+    ) -> Result<Account, PersistenceError> {
+        let mut conn = self.pool.get().acquire().await?;
+
+        Self::load_account_with(&mut conn, account_id, baseline_date).await
+    }
+}
+
+impl AccountRepository {
+    /// Load an account through `conn`, which may be part of an open transaction.
+    pub async fn load_account_with(
+        conn: &mut PgConnection,
+        account_id: AccountId,
+        baseline_date: DateTime<Utc>,
+    ) -> Result<Account, PersistenceError> {
         // Account must exist
-        let _ = self.find_account(account_id).await?;
+        let account_dto = Self::find_account(conn, account_id).await?;
+        let currency = account_dto.currency()?;
+        let overdraft_limit = account_dto.overdraft_limit(currency);
+        let status = account_dto.status()?;
+        let owner_id = account_dto.owner_id();
 
-        let activities_dto = self
-            .load_activities_by_owner_since(account_id, baseline_date)
-            .await?;
+        let activities_dto =
+            Self::load_activities_by_owner_since(conn, account_id, baseline_date).await?;
 
         log::info!("activities_dto: {:?}", activities_dto);
 
-        let withdrawal_balance = self
-            .get_withdrawal_balance_until(account_id, baseline_date)
-            .await?
-            .unwrap_or(0);
+        let withdrawal_balance =
+            Self::get_withdrawal_balance_until(conn, account_id, baseline_date, currency).await?;
 
-        let deposit_balance = self
-            .get_deposit_balance_until(account_id, baseline_date)
-            .await?
-            .unwrap_or(0);
+        let deposit_balance =
+            Self::get_deposit_balance_until(conn, account_id, baseline_date, currency).await?;
 
-        let baseline_balance = Money(deposit_balance) - Money(withdrawal_balance);
+        let baseline_balance = deposit_balance
+            .checked_sub(withdrawal_balance)
+            .map_err(|e| PersistenceError::CorruptedData(e.to_string()))?;
 
         let activities = activities_dto
             .into_iter()
             .map(|dto| dto.try_into())
-            .collect::<Result<Vec<Activity>>>()?;
+            .collect::<Result<Vec<Activity>, _>>()?;
 
         let account = AccountBuilder::default()
             .id(account_id)
+            .currency(currency)
             .baseline_balance(baseline_balance)
             .activity_window(ActivityWindow::new(activities))
-            .build()?;
+            .overdraft_limit(overdraft_limit)
+            .status(status)
+            .owner_id(owner_id)
+            .build()
+            .map_err(|e| PersistenceError::CorruptedData(e.to_string()))?;
 
         Ok(account)
     }
-}
 
-impl AccountRepository {
-    pub async fn find_account(&self, id: AccountId) -> Result<AccountDto> {
-        let account: AccountDto = sqlx::query_as("SELECT id FROM account WHERE id = $1")
-            .bind(id.0 as i64)
-            .fetch_one(self.pool.get())
+    pub async fn find_account(
+        conn: &mut PgConnection,
+        id: AccountId,
+    ) -> Result<AccountDto, PersistenceError> {
+        let account: Option<AccountDto> = sqlx::query_as(
+            "SELECT currency, overdraft_limit, status, customer_id FROM account WHERE id = $1",
+        )
+        .bind(id.0 as i64)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        account.ok_or(PersistenceError::AccountNotFound(id))
+    }
+
+    /// Update the status of `account` through `conn`, which may be part of an open transaction.
+    pub async fn update_status_with(
+        conn: &mut PgConnection,
+        account: &Account,
+    ) -> Result<(), PersistenceError> {
+        let account_id = *account.id().ok_or(PersistenceError::MissingAccountId)?;
+
+        let result = sqlx::query("UPDATE account SET status = $1 WHERE id = $2")
+            .bind(account.status().code())
+            .bind(account_id.0 as i64)
+            .execute(&mut *conn)
             .await?;
 
-        Ok(account)
+        if result.rows_affected() == 0 {
+            return Err(PersistenceError::AccountNotFound(account_id));
+        }
+
+        Ok(())
     }
 
     pub async fn load_activities_by_owner_since(
-        &self,
+        conn: &mut PgConnection,
         id: AccountId,
         baseline_date: DateTime<Utc>,
-    ) -> Result<Vec<ActivityDto>> {
+    ) -> Result<Vec<ActivityDto>, PersistenceError> {
         let activities: Vec<ActivityDto> = sqlx::query_as(
             r#"
             SELECT
-                a.id, a.timestamp, a.owner_account_id, a.source_account_id, a.target_account_id, a.amount
+                a.id, a.timestamp, a.owner_account_id, a.source_account_id, a.target_account_id, a.amount, a.currency,
+                a.converted_amount, a.converted_currency, a.reverses_activity_id, a.transfer_id
             FROM
                 activity a
             WHERE
@@ -93,18 +136,19 @@ impl AccountRepository {
         )
         .bind(id.0 as i64)
         .bind(baseline_date)
-        .fetch_all(self.pool.get())
+        .fetch_all(&mut *conn)
         .await?;
 
         Ok(activities)
     }
 
     pub async fn get_withdrawal_balance_until(
-        &self,
+        conn: &mut PgConnection,
         id: AccountId,
         baseline_date: DateTime<Utc>,
-    ) -> Result<Option<i64>> {
-        let amount: Option<(BigDecimal,)> = sqlx::query_as(
+        currency: Currency,
+    ) -> Result<Money, PersistenceError> {
+        let (amount,): (BigDecimal,) = sqlx::query_as(
             r#"
             SELECT coalesce(sum(a.amount), 0.0)
             FROM
@@ -118,20 +162,21 @@ impl AccountRepository {
         .bind(id.0 as i64)
         .bind(id.0 as i64)
         .bind(baseline_date)
-        .fetch_optional(self.pool.get())
+        .fetch_one(&mut *conn)
         .await?;
 
-        Ok(amount.and_then(|(a,)| a.to_i64()))
+        money_from_minor_units(&amount, currency)
     }
 
     pub async fn get_deposit_balance_until(
-        &self,
+        conn: &mut PgConnection,
         id: AccountId,
         baseline_date: DateTime<Utc>,
-    ) -> Result<Option<i64>> {
-        let amount: Option<(BigDecimal,)> = sqlx::query_as(
+        currency: Currency,
+    ) -> Result<Money, PersistenceError> {
+        let (amount,): (BigDecimal,) = sqlx::query_as(
             r#"
-            SELECT coalesce(sum(a.amount), 0.0)
+            SELECT coalesce(sum(coalesce(a.converted_amount, a.amount)), 0.0)
             FROM
                 activity a
             WHERE
@@ -143,16 +188,40 @@ impl AccountRepository {
         .bind(id.0 as i64)
         .bind(id.0 as i64)
         .bind(baseline_date)
-        .fetch_optional(self.pool.get())
+        .fetch_one(&mut *conn)
         .await?;
 
-        Ok(amount.and_then(|(a,)| a.to_i64()))
+        money_from_minor_units(&amount, currency)
     }
 }
 
 #[derive(Debug, sqlx::FromRow)]
 pub struct AccountDto {
-    id: i64,
+    currency: String,
+    overdraft_limit: Option<i64>,
+    status: String,
+    customer_id: Option<i64>,
+}
+
+impl AccountDto {
+    pub fn currency(&self) -> Result<Currency, PersistenceError> {
+        parse_currency(&self.currency)
+    }
+
+    pub fn overdraft_limit(&self, currency: Currency) -> Option<Money> {
+        self.overdraft_limit
+            .map(|overdraft_limit| Money::new(overdraft_limit, currency))
+    }
+
+    pub fn owner_id(&self) -> Option<CustomerId> {
+        self.customer_id.map(|id| CustomerId(id as u64))
+    }
+
+    pub fn status(&self) -> Result<AccountStatus, PersistenceError> {
+        AccountStatus::from_code(&self.status).ok_or_else(|| {
+            PersistenceError::CorruptedData(format!("Unknown account status '{}'", self.status))
+        })
+    }
 }
 
 #[derive(Debug, sqlx::FromRow)]
@@ -163,30 +232,59 @@ pub struct ActivityDto {
     source_account_id: i64,
     target_account_id: i64,
     amount: i64,
+    currency: String,
+    converted_amount: Option<i64>,
+    converted_currency: Option<String>,
+    reverses_activity_id: Option<i64>,
+    transfer_id: Option<i64>,
 }
 
 impl TryInto<Activity> for ActivityDto {
-    type Error = anyhow::Error;
+    type Error = PersistenceError;
 
     fn try_into(self) -> Result<Activity, Self::Error> {
-        Ok(ActivityBuilder::default()
+        let converted_money = match (self.converted_amount, &self.converted_currency) {
+            (Some(amount), Some(currency)) => Some(Money::new(amount, parse_currency(currency)?)),
+            (None, None) => None,
+            _ => {
+                return Err(PersistenceError::CorruptedData(format!(
+                    "Activity {} converted amount and currency must be set together",
+                    self.id
+                )))
+            }
+        };
+
+        ActivityBuilder::default()
             .id(Some(ActivityId(self.id as u64)))
             .owner_account_id(AccountId(self.owner_account_id as u64))
             .source_account_id(AccountId(self.source_account_id as u64))
             .target_account_id(AccountId(self.target_account_id as u64))
             .timestamp(self.timestamp)
-            .money(Money(self.amount))
-            .build()?)
+            .money(Money::new(self.amount, parse_currency(&self.currency)?))
+            .converted_money(converted_money)
+            .reverses_activity_id(self.reverses_activity_id.map(|id| ActivityId(id as u64)))
+            .transfer_id(self.transfer_id.map(|id| TransferId(id as u64)))
+            .build()
+            .map_err(|e| PersistenceError::CorruptedData(e.to_string()))
     }
 }
 
+pub(super) fn parse_currency(code: &str) -> Result<Currency, PersistenceError> {
+    Currency::from_code(code)
+        .ok_or_else(|| PersistenceError::CorruptedData(format!("Unknown currency '{}'", code)))
+}
+
 #[cfg(test)]
 mod tests {
+    use anyhow::Result;
     use chrono::TimeZone;
     use rocket::tokio;
     use shaku::HasComponent;
 
-    use crate::infrastructure::tests::{self, testing_module};
+    use crate::{
+        domain::money::tests::eur,
+        infrastructure::tests::{self, testing_module},
+    };
 
     use super::*;
 
@@ -204,11 +302,12 @@ mod tests {
         // When
         let account = port.load_account(account_id, baseline_date).await?;
         let n_activities = account.activity_window().activities().len();
-        let balance = account.calculate_balance();
+        let balance = account.calculate_balance()?;
 
         // Expect
         assert_eq!(n_activities, 2);
-        assert_eq!(balance, Money(500));
+        assert_eq!(balance, eur(500));
+        assert_eq!(account.currency(), Currency::EUR);
         Ok(())
     }
 }
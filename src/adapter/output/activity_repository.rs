@@ -0,0 +1,386 @@
+use std::{convert::TryInto, sync::Arc};
+
+use sqlx::PgConnection;
+
+use super::ActivityDto;
+use crate::{
+    application::port::output::{
+        LoadActivityPort, LoadTransferPort, PersistenceError, UpdateAccountStatePort,
+    },
+    domain::{
+        account::{Account, AccountBuilder},
+        activity::{Activity, ActivityId, ActivityWindow, TransferId},
+        money::Money,
+    },
+    infrastructure::db::DataSource,
+};
+
+#[derive(Component)]
+#[shaku(interface = UpdateAccountStatePort)]
+pub struct ActivityRepository {
+    #[shaku(inject)]
+    pool: Arc<dyn DataSource>,
+}
+
+#[rocket::async_trait]
+impl UpdateAccountStatePort for ActivityRepository {
+    async fn update_activities(&self, account: &Account) -> Result<Account, PersistenceError> {
+        let mut tx = self.pool.get().begin().await?;
+        let account = Self::update_activities_with(&mut tx, account).await?;
+        tx.commit().await?;
+
+        Ok(account)
+    }
+}
+
+/// `LoadActivityPort` reading single activities, whichever account owns them.
+#[derive(Component)]
+#[shaku(interface = LoadActivityPort)]
+pub struct LoadActivityRepository {
+    #[shaku(inject)]
+    pool: Arc<dyn DataSource>,
+}
+
+#[rocket::async_trait]
+impl LoadActivityPort for LoadActivityRepository {
+    async fn load_activity(&self, activity_id: ActivityId) -> Result<Activity, PersistenceError> {
+        let mut conn = self.pool.get().acquire().await?;
+
+        ActivityRepository::load_activity_with(&mut conn, activity_id).await
+    }
+
+    async fn load_activities(&self) -> Result<Vec<Activity>, PersistenceError> {
+        let activities: Vec<ActivityDto> = sqlx::query_as(
+            r#"
+            SELECT
+                a.id, a.timestamp, a.owner_account_id, a.source_account_id, a.target_account_id, a.amount, a.currency,
+                a.converted_amount, a.converted_currency, a.reverses_activity_id, a.transfer_id
+            FROM
+                activity a
+            ORDER BY
+                a.id
+            "#,
+        )
+        .fetch_all(self.pool.get())
+        .await?;
+
+        activities.into_iter().map(TryInto::try_into).collect()
+    }
+}
+
+/// `LoadTransferPort` reading both activities recording a transfer.
+#[derive(Component)]
+#[shaku(interface = LoadTransferPort)]
+pub struct LoadTransferRepository {
+    #[shaku(inject)]
+    pool: Arc<dyn DataSource>,
+}
+
+#[rocket::async_trait]
+impl LoadTransferPort for LoadTransferRepository {
+    async fn load_transfer(
+        &self,
+        transfer_id: TransferId,
+    ) -> Result<Vec<Activity>, PersistenceError> {
+        let activities: Vec<ActivityDto> = sqlx::query_as(
+            r#"
+            SELECT
+                a.id, a.timestamp, a.owner_account_id, a.source_account_id, a.target_account_id, a.amount, a.currency,
+                a.converted_amount, a.converted_currency, a.reverses_activity_id, a.transfer_id
+            FROM
+                activity a
+            WHERE
+                a.transfer_id = $1
+            ORDER BY
+                a.id
+            "#,
+        )
+        .bind(transfer_id.0 as i64)
+        .fetch_all(self.pool.get())
+        .await?;
+
+        if activities.is_empty() {
+            return Err(PersistenceError::TransferNotFound(transfer_id));
+        }
+
+        activities.into_iter().map(TryInto::try_into).collect()
+    }
+}
+
+impl ActivityRepository {
+    pub async fn load_activity_with(
+        conn: &mut PgConnection,
+        activity_id: ActivityId,
+    ) -> Result<Activity, PersistenceError> {
+        let activity: Option<ActivityDto> = sqlx::query_as(
+            r#"
+            SELECT
+                a.id, a.timestamp, a.owner_account_id, a.source_account_id, a.target_account_id, a.amount, a.currency,
+                a.converted_amount, a.converted_currency, a.reverses_activity_id, a.transfer_id
+            FROM
+                activity a
+            WHERE
+                a.id = $1
+            "#,
+        )
+        .bind(activity_id.0 as i64)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        activity
+            .ok_or(PersistenceError::ActivityNotFound(activity_id))?
+            .try_into()
+    }
+
+    pub async fn find_reversal_with(
+        conn: &mut PgConnection,
+        activity_id: ActivityId,
+    ) -> Result<Option<ActivityId>, PersistenceError> {
+        // A transfer may have been reversed through either of the activities recording it
+        let reversal: Option<(i64,)> = sqlx::query_as(
+            r#"
+            SELECT r.id
+            FROM activity r
+            JOIN activity t ON r.reverses_activity_id = t.id
+            JOIN activity a ON t.id = a.id OR t.transfer_id = a.transfer_id
+            WHERE a.id = $1
+            ORDER BY r.id
+            LIMIT 1
+            "#,
+        )
+        .bind(activity_id.0 as i64)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(reversal.map(|(id,)| ActivityId(id as u64)))
+    }
+
+    /// Insert the new activities of `account` through `conn`, which may be part of an open
+    /// transaction.
+    pub async fn update_activities_with(
+        conn: &mut PgConnection,
+        account: &Account,
+    ) -> Result<Account, PersistenceError> {
+        let account_id = *account.id().ok_or(PersistenceError::MissingAccountId)?;
+
+        let mut activities = Vec::with_capacity(account.activity_window().activities().len());
+
+        for activity in account.activity_window().activities() {
+            let activity = match activity.id() {
+                Some(_) => activity.clone(),
+                None => {
+                    let (id,): (i64,) = sqlx::query_as(
+                        r#"
+                        INSERT INTO activity
+                            (timestamp, owner_account_id, source_account_id, target_account_id, amount, currency,
+                             converted_amount, converted_currency, reverses_activity_id, transfer_id)
+                        VALUES
+                            ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                        RETURNING id
+                        "#,
+                    )
+                    .bind(activity.timestamp())
+                    .bind(activity.owner_account_id().0 as i64)
+                    .bind(activity.source_account_id().0 as i64)
+                    .bind(activity.target_account_id().0 as i64)
+                    .bind(activity.money().amount())
+                    .bind(activity.money().currency().code())
+                    .bind(activity.converted_money().map(Money::amount))
+                    .bind(activity.converted_money().map(|m| m.currency().code()))
+                    .bind(activity.reverses_activity_id().map(|id| id.0 as i64))
+                    .bind(activity.transfer_id().map(|id| id.0 as i64))
+                    .fetch_one(&mut *conn)
+                    .await?;
+
+                    activity.clone().with_id(ActivityId(id as u64))
+                }
+            };
+
+            activities.push(activity);
+        }
+
+        let account = AccountBuilder::default()
+            .id(account_id)
+            .currency(account.currency())
+            .baseline_balance(*account.baseline_balance())
+            .activity_window(ActivityWindow::new(activities))
+            .overdraft_limit(account.overdraft_limit())
+            .status(account.status())
+            .owner_id(account.owner_id())
+            .build()
+            .map_err(|e| PersistenceError::CorruptedData(e.to_string()))?;
+
+        Ok(account)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use chrono::{TimeZone, Utc};
+    use rocket::tokio;
+    use shaku::HasComponent;
+
+    use crate::{
+        application::port::output::{IdGenerator, LoadAccountPort, UnitOfWorkPort},
+        domain::{account::AccountId, money::tests::eur},
+        infrastructure::tests::{self, testing_module},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_updates_activities() -> Result<()> {
+        // Init
+        tests::setup();
+        let module = testing_module().await.build();
+        let load_port: &dyn LoadAccountPort = module.resolve_ref();
+        let update_port: &dyn UpdateAccountStatePort = module.resolve_ref();
+
+        // Given
+        let account_id = AccountId(1);
+        let baseline_date = Utc.ymd(2018, 8, 10).and_hms(0, 0, 0);
+        let mut account = load_port.load_account(account_id, baseline_date).await?;
+        account.withdraw(eur(100), AccountId(2))?;
+
+        // When
+        let updated = update_port.update_activities(&account).await?;
+        let reloaded = load_port.load_account(account_id, baseline_date).await?;
+
+        // Expect
+        assert_eq!(updated.activity_window().activities().len(), 3);
+        assert!(updated
+            .activity_window()
+            .activities()
+            .iter()
+            .all(|a| a.id().is_some()));
+        assert_eq!(reloaded.activity_window().activities().len(), 3);
+        assert_eq!(reloaded.calculate_balance()?, eur(400));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_loads_activities_and_their_reversals() -> Result<()> {
+        // Init
+        tests::setup();
+        let module = testing_module().await.build();
+        let port: &dyn LoadActivityPort = module.resolve_ref();
+        let uow_port: &dyn UnitOfWorkPort = module.resolve_ref();
+
+        // When
+        let activity = port.load_activity(ActivityId(1)).await?;
+        let unknown = port.load_activity(ActivityId(99)).await;
+        let activities = port.load_activities().await?;
+
+        // Expect
+        assert_eq!(*activity.source_account_id(), AccountId(1));
+        assert_eq!(*activity.target_account_id(), AccountId(2));
+        assert_eq!(*activity.money(), eur(500));
+        assert!(matches!(
+            unknown,
+            Err(PersistenceError::ActivityNotFound(ActivityId(99)))
+        ));
+        assert!(activities.len() >= 8);
+        assert!(activities
+            .windows(2)
+            .all(|pair| pair[0].id().map(|id| id.0) < pair[1].id().map(|id| id.0)));
+
+        // When
+        let baseline_date = Utc.ymd(2018, 8, 10).and_hms(0, 0, 0);
+        let mut uow = uow_port.begin().await?;
+        let mut account = uow.load_account(AccountId(1), baseline_date).await?;
+        account.reverse(&activity, TransferId(99))?;
+        let reversal = uow.update_activities(&account).await?;
+        let found = uow.find_reversal(ActivityId(1)).await?;
+        uow.rollback().await?;
+
+        // Expect
+        let reversal_id = reversal
+            .activity_window()
+            .activities()
+            .iter()
+            .find(|a| a.reverses_activity_id() == Some(&ActivityId(1)))
+            .and_then(Activity::id)
+            .copied();
+        assert!(found.is_some());
+        assert_eq!(found, reversal_id);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_loads_both_activities_of_transfers() -> Result<()> {
+        // Init
+        tests::setup();
+        let module = testing_module().await.build();
+        let port: &dyn LoadTransferPort = module.resolve_ref();
+        let uow_port: &dyn UnitOfWorkPort = module.resolve_ref();
+        let id_generator: &dyn IdGenerator = module.resolve_ref();
+
+        // Given
+        let transfer_id = id_generator.next_transfer_id().await?;
+        let baseline_date = Utc.ymd(2018, 8, 10).and_hms(0, 0, 0);
+        let mut uow = uow_port.begin().await?;
+        let mut source = uow.load_account(AccountId(1), baseline_date).await?;
+        let mut target = uow.load_account(AccountId(2), baseline_date).await?;
+        source.withdraw_converted(eur(10), None, AccountId(2), Some(transfer_id))?;
+        target.deposit_converted(eur(10), None, AccountId(1), Some(transfer_id))?;
+        uow.update_activities(&source).await?;
+        uow.update_activities(&target).await?;
+        uow.commit().await?;
+
+        // When
+        let activities = port.load_transfer(transfer_id).await?;
+        let unknown = port.load_transfer(TransferId(0)).await;
+
+        // Expect
+        let owners: Vec<_> = activities.iter().map(|a| *a.owner_account_id()).collect();
+        assert_eq!(owners, vec![AccountId(1), AccountId(2)]);
+        assert!(activities
+            .iter()
+            .all(|a| a.transfer_id() == Some(&transfer_id) && *a.money() == eur(10)));
+        assert!(matches!(
+            unknown,
+            Err(PersistenceError::TransferNotFound(TransferId(0)))
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_finds_reversals_through_either_activity_of_transfers() -> Result<()> {
+        // Init
+        tests::setup();
+        let module = testing_module().await.build();
+        let port: &dyn LoadTransferPort = module.resolve_ref();
+        let uow_port: &dyn UnitOfWorkPort = module.resolve_ref();
+        let id_generator: &dyn IdGenerator = module.resolve_ref();
+
+        // Given
+        let transfer_id = id_generator.next_transfer_id().await?;
+        let baseline_date = Utc.ymd(2018, 8, 10).and_hms(0, 0, 0);
+        let mut uow = uow_port.begin().await?;
+        let mut source = uow.load_account(AccountId(1), baseline_date).await?;
+        let mut target = uow.load_account(AccountId(2), baseline_date).await?;
+        source.withdraw_converted(eur(10), None, AccountId(2), Some(transfer_id))?;
+        target.deposit_converted(eur(10), None, AccountId(1), Some(transfer_id))?;
+        uow.update_activities(&source).await?;
+        uow.update_activities(&target).await?;
+        uow.commit().await?;
+        let activities = port.load_transfer(transfer_id).await?;
+        let (withdrawal, deposit) = (&activities[0], &activities[1]);
+
+        // When
+        let reversal_id = id_generator.next_transfer_id().await?;
+        let mut uow = uow_port.begin().await?;
+        let not_reversed = uow.find_reversal(*withdrawal.id().unwrap()).await?;
+        let mut target = uow.load_account(AccountId(2), baseline_date).await?;
+        target.reverse(deposit, reversal_id)?;
+        uow.update_activities(&target).await?;
+        let found = uow.find_reversal(*withdrawal.id().unwrap()).await?;
+        uow.rollback().await?;
+
+        // Expect
+        assert_eq!(not_reversed, None);
+        assert!(found.is_some());
+        Ok(())
+    }
+}
@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use crate::{
+    application::port::output::{CreateAccountPort, PersistenceError},
+    domain::account::Account,
+    infrastructure::db::DataSource,
+};
+
+#[derive(Component)]
+#[shaku(interface = CreateAccountPort)]
+pub struct CreateAccountRepository {
+    #[shaku(inject)]
+    pool: Arc<dyn DataSource>,
+}
+
+#[rocket::async_trait]
+impl CreateAccountPort for CreateAccountRepository {
+    async fn create_account(&self, account: &Account) -> Result<(), PersistenceError> {
+        let account_id = *account.id().ok_or(PersistenceError::MissingAccountId)?;
+
+        let mut tx = self.pool.get().begin().await?;
+        let result = sqlx::query(
+            r#"
+            INSERT INTO account (id, currency, overdraft_limit, status, customer_id)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (id) DO NOTHING
+            "#,
+        )
+        .bind(account_id.0 as i64)
+        .bind(account.currency().code())
+        .bind(account.overdraft_limit().map(|limit| limit.amount()))
+        .bind(account.status().code())
+        .bind(account.owner_id().map(|id| id.0 as i64))
+        .execute(&mut tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(PersistenceError::AccountAlreadyExists(account_id));
+        }
+
+        // Accounts opened with an explicit id must not collide with generated ones later on
+        sqlx::query(
+            r#"
+            SELECT setval('account_id_seq', greatest(last_value, $1))
+            FROM account_id_seq
+            "#,
+        )
+        .bind(account_id.0 as i64)
+        .execute(&mut tx)
+        .await?;
+
+        Ok(tx.commit().await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use chrono::Utc;
+    use rocket::tokio;
+    use shaku::HasComponent;
+
+    use crate::{
+        application::port::output::{IdGenerator, LoadAccountPort},
+        domain::{
+            account::{AccountBuilder, AccountId, AccountStatus},
+            activity::ActivityWindow,
+            money::{Currency, Money},
+        },
+        infrastructure::tests::{self, testing_module},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_creates_accounts_with_generated_ids() -> Result<()> {
+        // Init
+        tests::setup();
+        let module = testing_module().await.build();
+        let id_generator: &dyn IdGenerator = module.resolve_ref();
+        let port: &dyn CreateAccountPort = module.resolve_ref();
+        let load_port: &dyn LoadAccountPort = module.resolve_ref();
+
+        // Given
+        let account = AccountBuilder::default()
+            .id(id_generator.next_account_id().await?)
+            .baseline_balance(Money::zero(Currency::USD))
+            .activity_window(ActivityWindow::new(vec![]))
+            .build()?;
+
+        // When
+        port.create_account(&account).await?;
+        let duplicate = port.create_account(&account).await;
+
+        // Expect
+        let loaded = load_port
+            .load_account(*account.id().unwrap(), Utc::now())
+            .await?;
+        assert_eq!(loaded.currency(), Currency::USD);
+        assert_eq!(loaded.status(), AccountStatus::Active);
+        assert!(matches!(
+            duplicate,
+            Err(PersistenceError::AccountAlreadyExists(_))
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_explicit_id_ahead_of_sequence_then_generated_ids_are_past_it() -> Result<()> {
+        // Init
+        tests::setup();
+        let module = testing_module().await.build();
+        let id_generator: &dyn IdGenerator = module.resolve_ref();
+        let port: &dyn CreateAccountPort = module.resolve_ref();
+
+        // Given
+        let explicit_id = AccountId(id_generator.next_account_id().await?.0 + 10);
+        let account = Account::open(explicit_id, Currency::EUR, None);
+        port.create_account(&account).await?;
+
+        // When
+        let generated_id = id_generator.next_account_id().await?;
+
+        // Expect
+        assert!(generated_id > explicit_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use crate::{
+    application::port::output::{LoadCustomerPort, PersistenceError},
+    domain::{
+        account::AccountId,
+        customer::{Customer, CustomerId},
+    },
+    infrastructure::db::DataSource,
+};
+
+#[derive(Component)]
+#[shaku(interface = LoadCustomerPort)]
+pub struct CustomerRepository {
+    #[shaku(inject)]
+    pool: Arc<dyn DataSource>,
+}
+
+#[rocket::async_trait]
+impl LoadCustomerPort for CustomerRepository {
+    async fn load_customer(&self, customer_id: CustomerId) -> Result<Customer, PersistenceError> {
+        let mut conn = self.pool.get().acquire().await?;
+
+        let customer: Option<(i64, String)> =
+            sqlx::query_as("SELECT id, name FROM customer WHERE id = $1")
+                .bind(customer_id.0 as i64)
+                .fetch_optional(&mut conn)
+                .await?;
+        let (_, name) = customer.ok_or(PersistenceError::CustomerNotFound(customer_id))?;
+
+        let account_ids: Vec<(i64,)> =
+            sqlx::query_as("SELECT id FROM account WHERE customer_id = $1 ORDER BY id")
+                .bind(customer_id.0 as i64)
+                .fetch_all(&mut conn)
+                .await?;
+
+        Ok(Customer::new(
+            customer_id,
+            name,
+            account_ids
+                .into_iter()
+                .map(|(id,)| AccountId(id as u64))
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use rocket::tokio;
+    use shaku::HasComponent;
+
+    use crate::infrastructure::tests::{self, testing_module};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_loads_customer_with_accounts() -> Result<()> {
+        // Init
+        tests::setup();
+        let module = testing_module().await.build();
+        let port: &dyn LoadCustomerPort = module.resolve_ref();
+
+        // When
+        let customer = port.load_customer(CustomerId(1)).await?;
+        let unknown = port.load_customer(CustomerId(99)).await;
+
+        // Expect
+        assert_eq!(customer.name(), "Ada Lovelace");
+        assert_eq!(customer.account_ids(), &[AccountId(1), AccountId(2)]);
+        assert!(matches!(
+            unknown,
+            Err(PersistenceError::CustomerNotFound(CustomerId(99)))
+        ));
+        Ok(())
+    }
+}
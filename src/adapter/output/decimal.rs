@@ -0,0 +1,56 @@
+//! Conversions to `Money` of the `NUMERIC` values returned by PostgreSQL, e.g. sums of stored
+//! amounts.
+
+use num_traits::ToPrimitive;
+use sqlx::types::BigDecimal;
+
+use crate::{
+    application::port::output::PersistenceError,
+    domain::money::{Currency, Money},
+};
+
+/// Convert a decimal amount in minor units of `currency`, e.g. a `sum` of stored amounts, to
+/// `Money`.
+pub fn money_from_minor_units(
+    amount: &BigDecimal,
+    currency: Currency,
+) -> Result<Money, PersistenceError> {
+    if !amount.is_integer() {
+        return Err(PersistenceError::CorruptedData(format!(
+            "{} has more decimal digits than {} allows",
+            amount, currency
+        )));
+    }
+
+    amount
+        .to_i64()
+        .map(|amount| Money::new(amount, currency))
+        .ok_or_else(|| {
+            PersistenceError::CorruptedData(format!("{} overflows {} amounts", amount, currency))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::domain::money::tests::eur;
+
+    use super::*;
+
+    #[test]
+    fn it_converts_minor_units_to_money() {
+        assert_eq!(
+            money_from_minor_units(&BigDecimal::from(1234), Currency::EUR).unwrap(),
+            eur(1234)
+        );
+        assert!(
+            money_from_minor_units(&BigDecimal::from_str("0.5").unwrap(), Currency::EUR).is_err()
+        );
+        assert!(money_from_minor_units(
+            &(BigDecimal::from(i64::MAX) + BigDecimal::from(1)),
+            Currency::EUR
+        )
+        .is_err());
+    }
+}
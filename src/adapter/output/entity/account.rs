@@ -1,23 +1,38 @@
-//! SeaORM Entity. Generated by sea-orm-codegen 0.2.4
+//! SeaORM Entity of the `account` table.
 
 use sea_orm::entity::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 #[sea_orm(table_name = "account")]
 pub struct Model {
-    #[sea_orm(primary_key, auto_increment = false)]
+    #[sea_orm(primary_key)]
     pub id: i64,
+    pub currency: String,
+    pub overdraft_limit: Option<i64>,
+    pub status: String,
+    pub customer_id: Option<i64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter)]
-pub enum Relation {}
+pub enum Relation {
+    Customer,
+}
 
 impl RelationTrait for Relation {
     fn def(&self) -> RelationDef {
         match self {
-            _ => panic!("No RelationDef"),
+            Self::Customer => Entity::belongs_to(super::customer::Entity)
+                .from(Column::CustomerId)
+                .to(super::customer::Column::Id)
+                .into(),
         }
     }
 }
 
+impl Related<super::customer::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Customer.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
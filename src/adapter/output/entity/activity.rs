@@ -1,26 +1,37 @@
-//! SeaORM Entity. Generated by sea-orm-codegen 0.2.4
+//! SeaORM Entity of the `activity` table.
 
 use sea_orm::entity::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 #[sea_orm(table_name = "activity")]
 pub struct Model {
-    #[sea_orm(primary_key, auto_increment = false)]
+    #[sea_orm(primary_key)]
     pub id: i64,
     pub timestamp: DateTimeWithTimeZone,
     pub owner_account_id: Option<i64>,
     pub source_account_id: Option<i64>,
     pub target_account_id: Option<i64>,
     pub amount: Option<i64>,
+    pub currency: String,
+    pub converted_amount: Option<i64>,
+    pub converted_currency: Option<String>,
+    pub reverses_activity_id: Option<i64>,
+    pub transfer_id: Option<i64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter)]
-pub enum Relation {}
+pub enum Relation {
+    /// Activity compensated by this one, if it reverses a transfer.
+    SelfRef,
+}
 
 impl RelationTrait for Relation {
     fn def(&self) -> RelationDef {
         match self {
-            _ => panic!("No RelationDef"),
+            Self::SelfRef => Entity::belongs_to(Entity)
+                .from(Column::ReversesActivityId)
+                .to(Column::Id)
+                .into(),
         }
     }
 }
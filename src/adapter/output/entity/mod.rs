@@ -1,6 +1,9 @@
-//! SeaORM Entity. Generated by sea-orm-codegen 0.2.4
+//! SeaORM Entities of the tables created by `migrations`, kept in sync with them by hand.
 
 pub mod prelude;
 
 pub mod account;
 pub mod activity;
+pub mod customer;
+pub mod idempotency_key;
+pub mod scheduled_transfer;
@@ -1,4 +1,5 @@
-//! SeaORM Entity. Generated by sea-orm-codegen 0.2.4
-
 pub use super::account::Entity as Account;
 pub use super::activity::Entity as Activity;
+pub use super::customer::Entity as Customer;
+pub use super::idempotency_key::Entity as IdempotencyKey;
+pub use super::scheduled_transfer::Entity as ScheduledTransfer;
@@ -0,0 +1,9 @@
+//! Conversions of database errors to the errors returned by output ports.
+
+use crate::application::port::output::PersistenceError;
+
+impl From<sqlx::Error> for PersistenceError {
+    fn from(err: sqlx::Error) -> Self {
+        PersistenceError::Database(Box::new(err))
+    }
+}
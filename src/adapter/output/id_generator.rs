@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use crate::{
+    application::port::output::{IdGenerator, PersistenceError},
+    domain::{account::AccountId, activity::TransferId},
+    infrastructure::db::DataSource,
+};
+
+/// `IdGenerator` drawing ids from Postgres sequences.
+#[derive(Component)]
+#[shaku(interface = IdGenerator)]
+pub struct PostgresIdGenerator {
+    #[shaku(inject)]
+    pool: Arc<dyn DataSource>,
+}
+
+#[rocket::async_trait]
+impl IdGenerator for PostgresIdGenerator {
+    async fn next_account_id(&self) -> Result<AccountId, PersistenceError> {
+        let (id,): (i64,) = sqlx::query_as("SELECT nextval('account_id_seq')")
+            .fetch_one(self.pool.get())
+            .await?;
+
+        Ok(AccountId(id as u64))
+    }
+
+    async fn next_transfer_id(&self) -> Result<TransferId, PersistenceError> {
+        let (id,): (i64,) = sqlx::query_as("SELECT nextval('transfer_id_seq')")
+            .fetch_one(self.pool.get())
+            .await?;
+
+        Ok(TransferId(id as u64))
+    }
+}
@@ -0,0 +1,173 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgConnection;
+
+use super::account_repository::parse_currency;
+use crate::{
+    application::port::output::{IdempotencyKey, IdempotencyRecord, PersistenceError},
+    domain::{account::AccountId, money::Money},
+};
+
+/// Queries of the `idempotency_key` table, run by units of work moving money.
+pub struct IdempotencyRepository;
+
+impl IdempotencyRepository {
+    pub async fn find_with(
+        conn: &mut PgConnection,
+        source_account_id: AccountId,
+        key: &IdempotencyKey,
+        since: DateTime<Utc>,
+    ) -> Result<Option<IdempotencyRecord>, PersistenceError> {
+        let record: Option<IdempotencyRecordDto> = sqlx::query_as(
+            r#"
+            SELECT key, source_account_id, target_account_id, amount, currency, created_at
+            FROM idempotency_key
+            WHERE source_account_id = $1
+            AND key = $2
+            AND created_at >= $3
+            "#,
+        )
+        .bind(source_account_id.0 as i64)
+        .bind(key.0.as_str())
+        .bind(since)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        record.map(IdempotencyRecordDto::into_record).transpose()
+    }
+
+    /// Insert `record` through `conn`, replacing a record of the same source account and key
+    /// left before `since`. Concurrent inserts of the same key wait for each other, so only one
+    /// of them succeeds.
+    pub async fn save_with(
+        conn: &mut PgConnection,
+        record: &IdempotencyRecord,
+        since: DateTime<Utc>,
+    ) -> Result<(), PersistenceError> {
+        let saved = sqlx::query(
+            r#"
+            INSERT INTO idempotency_key
+                (key, source_account_id, target_account_id, amount, currency, created_at)
+            VALUES
+                ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (source_account_id, key) DO UPDATE
+            SET target_account_id = EXCLUDED.target_account_id,
+                amount = EXCLUDED.amount,
+                currency = EXCLUDED.currency,
+                created_at = EXCLUDED.created_at
+            WHERE idempotency_key.created_at < $7
+            "#,
+        )
+        .bind(record.key().0.as_str())
+        .bind(record.source_account_id().0 as i64)
+        .bind(record.target_account_id().0 as i64)
+        .bind(record.money().amount())
+        .bind(record.money().currency().code())
+        .bind(record.created_at())
+        .bind(since)
+        .execute(&mut *conn)
+        .await?;
+
+        if saved.rows_affected() == 0 {
+            return Err(PersistenceError::IdempotencyKeyTaken(record.key().clone()));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct IdempotencyRecordDto {
+    key: String,
+    source_account_id: i64,
+    target_account_id: i64,
+    amount: i64,
+    currency: String,
+    created_at: DateTime<Utc>,
+}
+
+impl IdempotencyRecordDto {
+    fn into_record(self) -> Result<IdempotencyRecord, PersistenceError> {
+        let money = Money::new(self.amount, parse_currency(&self.currency)?);
+
+        Ok(IdempotencyRecord::new(
+            IdempotencyKey(self.key),
+            AccountId(self.source_account_id as u64),
+            AccountId(self.target_account_id as u64),
+            money,
+            self.created_at,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use chrono::Duration;
+    use rocket::tokio;
+    use shaku::HasComponent;
+
+    use crate::{
+        application::port::output::UnitOfWorkPort,
+        domain::money::tests::eur,
+        infrastructure::tests::{self, testing_module},
+    };
+
+    use super::*;
+
+    fn record(source: u64, key: &str, created_at: DateTime<Utc>) -> IdempotencyRecord {
+        IdempotencyRecord::new(
+            IdempotencyKey(key.to_owned()),
+            AccountId(source),
+            AccountId(2),
+            eur(500),
+            created_at,
+        )
+    }
+
+    #[tokio::test]
+    async fn it_saves_records_once_per_source_account() -> Result<()> {
+        // Init
+        tests::setup();
+        let module = testing_module().await.build();
+        let port: &dyn UnitOfWorkPort = module.resolve_ref();
+
+        // Given
+        let now = Utc::now();
+        let since = now - Duration::hours(1);
+        let key = IdempotencyKey("repository".into());
+        let mut uow = port.begin().await?;
+        uow.save_idempotency_record(&record(1, "repository", now - Duration::hours(2)), since)
+            .await?;
+
+        // Expect
+        assert_eq!(
+            uow.find_idempotency_record(AccountId(1), &key, since)
+                .await?,
+            None
+        );
+
+        // When
+        uow.save_idempotency_record(&record(1, "repository", now), since)
+            .await?;
+        uow.save_idempotency_record(&record(3, "repository", now), since)
+            .await?;
+        let taken = uow
+            .save_idempotency_record(&record(1, "repository", now), since)
+            .await;
+        let found = uow
+            .find_idempotency_record(AccountId(1), &key, since)
+            .await?;
+        uow.rollback().await?;
+
+        // Expect
+        assert!(matches!(
+            taken,
+            Err(PersistenceError::IdempotencyKeyTaken(IdempotencyKey(key))) if key == "repository"
+        ));
+        let found = found.unwrap();
+        assert_eq!(found.key(), &key);
+        assert_eq!(found.source_account_id(), &AccountId(1));
+        assert!(found.matches(&AccountId(2), &eur(500)));
+        Ok(())
+    }
+}
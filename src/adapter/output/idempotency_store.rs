@@ -0,0 +1,194 @@
+use std::{collections::HashMap, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+
+use crate::{
+    application::port::output::{
+        IdempotencyKey, IdempotencyRecord, IdempotencyStore, PersistenceError,
+    },
+    domain::account::AccountId,
+};
+
+/// Idempotency records committed by every `InMemoryIdempotencyStore` of the running process.
+#[derive(Default)]
+pub struct InMemoryIdempotencyRecords {
+    records: Mutex<HashMap<(AccountId, IdempotencyKey), IdempotencyRecord>>,
+}
+
+impl InMemoryIdempotencyRecords {
+    /// Get an `IdempotencyStore` whose saved records are visible to others once committed.
+    pub fn store(self: &Arc<Self>) -> InMemoryIdempotencyStore {
+        InMemoryIdempotencyStore {
+            records: self.clone(),
+            staged: vec![],
+        }
+    }
+
+    /// Get every committed record.
+    pub fn records(&self) -> Vec<IdempotencyRecord> {
+        self.records.lock().values().cloned().collect()
+    }
+
+    fn find(
+        &self,
+        source_account_id: AccountId,
+        key: &IdempotencyKey,
+        since: DateTime<Utc>,
+    ) -> Option<IdempotencyRecord> {
+        self.records
+            .lock()
+            .get(&(source_account_id, key.clone()))
+            .filter(|record| *record.created_at() >= since)
+            .cloned()
+    }
+}
+
+/// `IdempotencyStore` keeping records in memory, only safe when running a single instance.
+///
+/// Saved records are staged until `commit` is called, and discarded when the store is dropped.
+pub struct InMemoryIdempotencyStore {
+    records: Arc<InMemoryIdempotencyRecords>,
+    staged: Vec<(IdempotencyRecord, DateTime<Utc>)>,
+}
+
+impl InMemoryIdempotencyStore {
+    /// Publish the staged records, failing with `IdempotencyKeyTaken` without publishing any if
+    /// another store committed one of their keys in the meantime.
+    pub fn commit(self) -> Result<(), PersistenceError> {
+        let mut records = self.records.records.lock();
+        for (record, since) in &self.staged {
+            let id = (*record.source_account_id(), record.key().clone());
+            if records.get(&id).is_some_and(|r| *r.created_at() >= *since) {
+                return Err(PersistenceError::IdempotencyKeyTaken(record.key().clone()));
+            }
+        }
+
+        for (record, _) in self.staged {
+            records.insert((*record.source_account_id(), record.key().clone()), record);
+        }
+        Ok(())
+    }
+}
+
+#[rocket::async_trait]
+impl IdempotencyStore for InMemoryIdempotencyStore {
+    async fn find_idempotency_record(
+        &mut self,
+        source_account_id: AccountId,
+        key: &IdempotencyKey,
+        since: DateTime<Utc>,
+    ) -> Result<Option<IdempotencyRecord>, PersistenceError> {
+        let staged = self
+            .staged
+            .iter()
+            .map(|(record, _)| record)
+            .filter(|record| record.source_account_id() == &source_account_id)
+            .find(|record| record.key() == key && *record.created_at() >= since)
+            .cloned();
+
+        Ok(staged.or_else(|| self.records.find(source_account_id, key, since)))
+    }
+
+    async fn save_idempotency_record(
+        &mut self,
+        record: &IdempotencyRecord,
+        since: DateTime<Utc>,
+    ) -> Result<(), PersistenceError> {
+        let taken = self
+            .find_idempotency_record(*record.source_account_id(), record.key(), since)
+            .await?;
+        if taken.is_some() {
+            return Err(PersistenceError::IdempotencyKeyTaken(record.key().clone()));
+        }
+
+        self.staged.push((record.clone(), since));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use chrono::Duration;
+    use rocket::tokio;
+
+    use crate::domain::money::tests::eur;
+
+    use super::*;
+
+    fn record(source_account_id: u64, created_at: DateTime<Utc>) -> IdempotencyRecord {
+        IdempotencyRecord::new(
+            IdempotencyKey("transfer-1".into()),
+            AccountId(source_account_id),
+            AccountId(2),
+            eur(100),
+            created_at,
+        )
+    }
+
+    #[tokio::test]
+    async fn it_publishes_records_once_committed() -> Result<()> {
+        // Given
+        let now = Utc::now();
+        let since = now - Duration::hours(1);
+        let key = IdempotencyKey("transfer-1".into());
+        let records = Arc::new(InMemoryIdempotencyRecords::default());
+        let mut store = records.store();
+        let mut other = records.store();
+
+        // When
+        store
+            .save_idempotency_record(&record(1, now), since)
+            .await?;
+        let staged = other
+            .find_idempotency_record(AccountId(1), &key, since)
+            .await?;
+        store.commit()?;
+
+        // Expect
+        assert!(staged.is_none());
+        let committed = other
+            .find_idempotency_record(AccountId(1), &key, since)
+            .await?;
+        assert_eq!(committed, Some(record(1, now)));
+        assert!(records.store().commit().is_ok());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_refuses_keys_taken_since_and_replaces_older_records() -> Result<()> {
+        // Given
+        let now = Utc::now();
+        let since = now - Duration::hours(1);
+        let records = Arc::new(InMemoryIdempotencyRecords::default());
+        let mut store = records.store();
+        store
+            .save_idempotency_record(&record(1, now - Duration::hours(2)), since)
+            .await?;
+        store.commit()?;
+        let mut first = records.store();
+        let mut second = records.store();
+
+        // When
+        first
+            .save_idempotency_record(&record(1, now), since)
+            .await?;
+        second
+            .save_idempotency_record(&record(1, now), since)
+            .await?;
+        let other_account = first.save_idempotency_record(&record(3, now), since).await;
+        let committed = first.commit();
+        let concurrent = second.commit();
+
+        // Expect
+        assert!(other_account.is_ok());
+        assert!(committed.is_ok());
+        assert!(matches!(
+            concurrent,
+            Err(PersistenceError::IdempotencyKeyTaken(_))
+        ));
+        assert_eq!(records.records().len(), 2);
+        Ok(())
+    }
+}
@@ -1,4 +1,27 @@
+mod account_lock;
 mod account_repository;
+mod activity_repository;
+mod create_account_repository;
+mod customer_repository;
+mod decimal;
+mod error;
+mod id_generator;
+mod idempotency_repository;
+mod idempotency_store;
+mod scheduled_transfer_repository;
+mod static_exchange_rate;
+mod unit_of_work;
 
+pub use account_lock::*;
 pub use account_repository::*;
+pub use activity_repository::*;
+pub use create_account_repository::*;
+pub use customer_repository::*;
+pub use decimal::*;
+pub use id_generator::*;
+pub use idempotency_repository::*;
+pub use idempotency_store::*;
+pub use scheduled_transfer_repository::*;
+pub use static_exchange_rate::*;
+pub use unit_of_work::*;
 pub mod entity;
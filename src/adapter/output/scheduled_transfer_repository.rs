@@ -0,0 +1,218 @@
+use std::{convert::TryInto, sync::Arc};
+
+use chrono::{DateTime, Duration, Utc};
+
+use super::account_repository::parse_currency;
+use crate::{
+    application::port::output::{PersistenceError, ScheduledTransferPort},
+    domain::{
+        account::AccountId,
+        money::Money,
+        scheduled_transfer::{
+            ScheduledTransfer, ScheduledTransferBuilder, ScheduledTransferId,
+            ScheduledTransferStatus,
+        },
+    },
+    infrastructure::db::DataSource,
+};
+
+#[derive(Component)]
+#[shaku(interface = ScheduledTransferPort)]
+pub struct ScheduledTransferRepository {
+    #[shaku(inject)]
+    pool: Arc<dyn DataSource>,
+}
+
+#[rocket::async_trait]
+impl ScheduledTransferPort for ScheduledTransferRepository {
+    async fn schedule(
+        &self,
+        transfer: &ScheduledTransfer,
+    ) -> Result<ScheduledTransferId, PersistenceError> {
+        let (id,): (i64,) = sqlx::query_as(
+            r#"
+            INSERT INTO scheduled_transfer
+                (source_account_id, target_account_id, amount, currency, execute_at, status)
+            VALUES
+                ($1, $2, $3, $4, $5, $6)
+            RETURNING id
+            "#,
+        )
+        .bind(transfer.source_account_id().0 as i64)
+        .bind(transfer.target_account_id().0 as i64)
+        .bind(transfer.money().amount())
+        .bind(transfer.money().currency().code())
+        .bind(transfer.execute_at())
+        .bind(transfer.status().code())
+        .fetch_one(self.pool.get())
+        .await?;
+
+        Ok(ScheduledTransferId(id as u64))
+    }
+
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        claim_timeout: Duration,
+        limit: u32,
+    ) -> Result<Vec<ScheduledTransfer>, PersistenceError> {
+        // Rows locked by another worker are skipped rather than waited for
+        let transfers: Vec<ScheduledTransferDto> = sqlx::query_as(
+            r#"
+            UPDATE scheduled_transfer
+            SET status = 'executing', claimed_at = $1
+            WHERE id IN (
+                SELECT id
+                FROM scheduled_transfer
+                WHERE (status = 'pending' AND execute_at <= $1)
+                OR (status = 'executing' AND claimed_at < $2)
+                ORDER BY execute_at
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING
+                id, source_account_id, target_account_id, amount, currency, execute_at, status,
+                claimed_at, executed_at, failure
+            "#,
+        )
+        .bind(now)
+        .bind(now - claim_timeout)
+        .bind(limit as i64)
+        .fetch_all(self.pool.get())
+        .await?;
+
+        let mut transfers = transfers
+            .into_iter()
+            .map(|dto| dto.try_into())
+            .collect::<Result<Vec<ScheduledTransfer>, _>>()?;
+        transfers.sort_by_key(|transfer| *transfer.execute_at());
+
+        Ok(transfers)
+    }
+
+    async fn record_outcome(&self, transfer: &ScheduledTransfer) -> Result<(), PersistenceError> {
+        let id = transfer.id().ok_or_else(|| {
+            PersistenceError::CorruptedData("Scheduled transfer id is not set".into())
+        })?;
+
+        sqlx::query(
+            r#"
+            UPDATE scheduled_transfer
+            SET status = $1, executed_at = $2, failure = $3
+            WHERE id = $4
+            "#,
+        )
+        .bind(transfer.status().code())
+        .bind(transfer.executed_at())
+        .bind(transfer.failure())
+        .bind(id.0 as i64)
+        .execute(self.pool.get())
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ScheduledTransferDto {
+    id: i64,
+    source_account_id: i64,
+    target_account_id: i64,
+    amount: i64,
+    currency: String,
+    execute_at: DateTime<Utc>,
+    status: String,
+    claimed_at: Option<DateTime<Utc>>,
+    executed_at: Option<DateTime<Utc>>,
+    failure: Option<String>,
+}
+
+impl TryInto<ScheduledTransfer> for ScheduledTransferDto {
+    type Error = PersistenceError;
+
+    fn try_into(self) -> Result<ScheduledTransfer, Self::Error> {
+        let status = ScheduledTransferStatus::from_code(&self.status).ok_or_else(|| {
+            PersistenceError::CorruptedData(format!(
+                "Unknown scheduled transfer status '{}'",
+                self.status
+            ))
+        })?;
+
+        ScheduledTransferBuilder::default()
+            .id(Some(ScheduledTransferId(self.id as u64)))
+            .source_account_id(AccountId(self.source_account_id as u64))
+            .target_account_id(AccountId(self.target_account_id as u64))
+            .money(Money::new(self.amount, parse_currency(&self.currency)?))
+            .execute_at(self.execute_at)
+            .status(status)
+            .claimed_at(self.claimed_at)
+            .executed_at(self.executed_at)
+            .failure(self.failure)
+            .build()
+            .map_err(|e| PersistenceError::CorruptedData(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use rocket::tokio;
+    use shaku::HasComponent;
+
+    use crate::{
+        domain::money::tests::eur,
+        infrastructure::tests::{self, testing_module},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_claims_due_transfers_once() -> Result<()> {
+        // Init
+        tests::setup();
+        let module = testing_module().await.build();
+        let port: &dyn ScheduledTransferPort = module.resolve_ref();
+
+        // Given
+        let now = Utc::now();
+        let due = ScheduledTransfer::new(AccountId(1), AccountId(2), eur(100), now);
+        let later = ScheduledTransfer::new(
+            AccountId(1),
+            AccountId(2),
+            eur(100),
+            now + Duration::days(1),
+        );
+        let due_id = port.schedule(&due).await?;
+        port.schedule(&later).await?;
+
+        // When
+        let timeout = Duration::minutes(10);
+        let mut claimed = port.claim_due(now, timeout, 10).await?;
+        let claimed_again = port.claim_due(now, timeout, 10).await?;
+
+        // Expect
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].id(), Some(&due_id));
+        assert_eq!(claimed[0].status(), ScheduledTransferStatus::Executing);
+        assert!(claimed_again.is_empty());
+
+        // When
+        let reclaimed = port.claim_due(now + timeout * 2, timeout, 10).await?;
+
+        // Expect
+        assert_eq!(reclaimed.len(), 1);
+        assert_eq!(reclaimed[0].id(), Some(&due_id));
+        assert_eq!(reclaimed[0].claimed_at(), Some(&(now + timeout * 2)));
+
+        // When
+        claimed[0].mark_failed(now, "Insufficient funds".into());
+        port.record_outcome(&claimed[0]).await?;
+
+        // Expect
+        assert!(port
+            .claim_due(now + timeout * 4, timeout, 10)
+            .await?
+            .is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use crate::{
+    application::port::output::{ExchangeRateError, ExchangeRatePort},
+    domain::{exchange_rate::ExchangeRate, money::Currency},
+};
+
+/// `ExchangeRatePort` serving a fixed set of rates, e.g. read from configuration.
+#[derive(Component)]
+#[shaku(interface = ExchangeRatePort)]
+pub struct StaticExchangeRateAdapter {
+    #[shaku(default)]
+    rates: HashMap<(Currency, Currency), ExchangeRate>,
+}
+
+impl StaticExchangeRateAdapter {
+    pub fn new(rates: Vec<ExchangeRate>) -> Self {
+        Self {
+            rates: index_rates(rates),
+        }
+    }
+}
+
+impl From<Vec<ExchangeRate>> for StaticExchangeRateAdapterParameters {
+    fn from(rates: Vec<ExchangeRate>) -> Self {
+        Self {
+            rates: index_rates(rates),
+        }
+    }
+}
+
+fn index_rates(rates: Vec<ExchangeRate>) -> HashMap<(Currency, Currency), ExchangeRate> {
+    rates
+        .into_iter()
+        .map(|rate| ((rate.source(), rate.target()), rate))
+        .collect()
+}
+
+#[rocket::async_trait]
+impl ExchangeRatePort for StaticExchangeRateAdapter {
+    async fn exchange_rate(
+        &self,
+        source: Currency,
+        target: Currency,
+    ) -> Result<ExchangeRate, ExchangeRateError> {
+        if source == target {
+            return Ok(ExchangeRate::identity(source));
+        }
+
+        self.rates
+            .get(&(source, target))
+            .copied()
+            .ok_or(ExchangeRateError::Unavailable { source, target })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::tokio;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_serves_configured_rates() {
+        // Given
+        let eur_usd =
+            ExchangeRate::from_decimal_str(Currency::EUR, Currency::USD, "1.085").unwrap();
+        let adapter = StaticExchangeRateAdapter::new(vec![eur_usd]);
+
+        // Expect
+        assert_eq!(
+            adapter.exchange_rate(Currency::EUR, Currency::USD).await,
+            Ok(eur_usd)
+        );
+        assert_eq!(
+            adapter.exchange_rate(Currency::USD, Currency::USD).await,
+            Ok(ExchangeRate::identity(Currency::USD))
+        );
+        assert_eq!(
+            adapter.exchange_rate(Currency::USD, Currency::EUR).await,
+            Err(ExchangeRateError::Unavailable {
+                source: Currency::USD,
+                target: Currency::EUR,
+            })
+        );
+    }
+}
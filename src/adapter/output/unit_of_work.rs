@@ -0,0 +1,226 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Transaction};
+
+use crate::{
+    application::port::output::{
+        AccountLock, IdempotencyKey, IdempotencyRecord, IdempotencyStore, PersistenceError,
+        UnitOfWork, UnitOfWorkPort,
+    },
+    domain::{
+        account::{Account, AccountId},
+        activity::ActivityId,
+    },
+    infrastructure::db::DataSource,
+};
+
+use super::{
+    AccountRepository, ActivityRepository, IdempotencyRepository, InMemoryAccountLock,
+    InMemoryAccountLocks, PostgresAccountLock,
+};
+
+#[derive(Component)]
+#[shaku(interface = UnitOfWorkPort)]
+pub struct PostgresUnitOfWorkPort {
+    #[shaku(inject)]
+    pool: Arc<dyn DataSource>,
+    /// Locks accounts within the running process rather than through PostgreSQL, when set.
+    #[shaku(default)]
+    in_memory_account_locks: Option<Arc<InMemoryAccountLocks>>,
+}
+
+#[rocket::async_trait]
+impl UnitOfWorkPort for PostgresUnitOfWorkPort {
+    async fn begin(&self) -> Result<Box<dyn UnitOfWork>, PersistenceError> {
+        let tx = self.pool.get().begin().await?;
+
+        Ok(Box::new(PostgresUnitOfWork {
+            tx,
+            in_memory_account_lock: self.in_memory_account_locks.as_ref().map(|l| l.holder()),
+        }))
+    }
+}
+
+pub struct PostgresUnitOfWork {
+    tx: Transaction<'static, Postgres>,
+    in_memory_account_lock: Option<InMemoryAccountLock>,
+}
+
+#[rocket::async_trait]
+impl AccountLock for PostgresUnitOfWork {
+    async fn lock_account(&mut self, account_id: AccountId) -> Result<(), PersistenceError> {
+        match &mut self.in_memory_account_lock {
+            Some(lock) => lock.lock_account(account_id).await,
+            None => {
+                PostgresAccountLock::new(&mut self.tx)
+                    .lock_account(account_id)
+                    .await
+            }
+        }
+    }
+
+    async fn release_account(&mut self, account_id: AccountId) -> Result<(), PersistenceError> {
+        match &mut self.in_memory_account_lock {
+            Some(lock) => lock.release_account(account_id).await,
+            None => {
+                PostgresAccountLock::new(&mut self.tx)
+                    .release_account(account_id)
+                    .await
+            }
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl IdempotencyStore for PostgresUnitOfWork {
+    async fn find_idempotency_record(
+        &mut self,
+        source_account_id: AccountId,
+        key: &IdempotencyKey,
+        since: DateTime<Utc>,
+    ) -> Result<Option<IdempotencyRecord>, PersistenceError> {
+        IdempotencyRepository::find_with(&mut self.tx, source_account_id, key, since).await
+    }
+
+    async fn save_idempotency_record(
+        &mut self,
+        record: &IdempotencyRecord,
+        since: DateTime<Utc>,
+    ) -> Result<(), PersistenceError> {
+        IdempotencyRepository::save_with(&mut self.tx, record, since).await
+    }
+}
+
+#[rocket::async_trait]
+impl UnitOfWork for PostgresUnitOfWork {
+    async fn load_account(
+        &mut self,
+        account_id: AccountId,
+        baseline_date: DateTime<Utc>,
+    ) -> Result<Account, PersistenceError> {
+        AccountRepository::load_account_with(&mut self.tx, account_id, baseline_date).await
+    }
+
+    async fn update_activities(&mut self, account: &Account) -> Result<Account, PersistenceError> {
+        ActivityRepository::update_activities_with(&mut self.tx, account).await
+    }
+
+    async fn find_reversal(
+        &mut self,
+        activity_id: ActivityId,
+    ) -> Result<Option<ActivityId>, PersistenceError> {
+        ActivityRepository::find_reversal_with(&mut self.tx, activity_id).await
+    }
+
+    async fn update_status(&mut self, account: &Account) -> Result<(), PersistenceError> {
+        AccountRepository::update_status_with(&mut self.tx, account).await
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), PersistenceError> {
+        Ok(self.tx.commit().await?)
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), PersistenceError> {
+        Ok(self.tx.rollback().await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicBool, Ordering},
+        time::Duration,
+    };
+
+    use anyhow::Result;
+    use chrono::TimeZone;
+    use rocket::tokio::{self, time::timeout};
+    use shaku::HasComponent;
+
+    use crate::{
+        application::port::output::LoadAccountPort,
+        domain::money::tests::eur,
+        infrastructure::tests::{self, testing_module},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_commits_or_rolls_back_updates() -> Result<()> {
+        // Init
+        tests::setup();
+        let module = testing_module().await.build();
+        let port: &dyn UnitOfWorkPort = module.resolve_ref();
+        let load_port: &dyn LoadAccountPort = module.resolve_ref();
+
+        // Given
+        let baseline_date = Utc.ymd(2018, 8, 10).and_hms(0, 0, 0);
+
+        // When
+        let mut uow = port.begin().await?;
+        let mut account = uow.load_account(AccountId(1), baseline_date).await?;
+        account.withdraw(eur(100), AccountId(2))?;
+        uow.update_activities(&account).await?;
+        uow.rollback().await?;
+
+        // Expect
+        let account = load_port.load_account(AccountId(1), baseline_date).await?;
+        assert_eq!(account.activity_window().activities().len(), 2);
+
+        // When
+        let mut uow = port.begin().await?;
+        let mut source = uow.load_account(AccountId(1), baseline_date).await?;
+        let mut target = uow.load_account(AccountId(2), baseline_date).await?;
+        source.withdraw(eur(100), AccountId(2))?;
+        target.deposit(eur(100), AccountId(1))?;
+        uow.update_activities(&source).await?;
+        uow.update_activities(&target).await?;
+        uow.commit().await?;
+
+        // Expect
+        let source = load_port.load_account(AccountId(1), baseline_date).await?;
+        let target = load_port.load_account(AccountId(2), baseline_date).await?;
+        assert_eq!(source.calculate_balance()?, eur(400));
+        assert_eq!(target.calculate_balance()?, eur(-400));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_locks_accounts_until_committed_or_rolled_back() -> Result<()> {
+        // Init
+        tests::setup();
+        let module = testing_module().await.build();
+        let port: Arc<dyn UnitOfWorkPort> = module.resolve();
+
+        // Given
+        let mut uow = port.begin().await?;
+        uow.lock_accounts(&[AccountId(2), AccountId(1)]).await?;
+
+        // Expect another account can still be locked
+        let mut other = port.begin().await?;
+        timeout(Duration::from_secs(1), other.lock_accounts(&[AccountId(3)])).await??;
+        other.rollback().await?;
+
+        // When the same account is locked by another unit of work
+        let acquired = Arc::new(AtomicBool::new(false));
+        let contender = tokio::spawn({
+            let port = port.clone();
+            let acquired = acquired.clone();
+            async move {
+                let mut uow = port.begin().await?;
+                uow.lock_accounts(&[AccountId(1)]).await?;
+                acquired.store(true, Ordering::SeqCst);
+                uow.rollback().await
+            }
+        });
+
+        // Expect it waits until the first one is committed
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!acquired.load(Ordering::SeqCst));
+        uow.commit().await?;
+        timeout(Duration::from_secs(1), contender).await???;
+        assert!(acquired.load(Ordering::SeqCst));
+        Ok(())
+    }
+}
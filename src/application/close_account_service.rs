@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+
+use crate::domain::account::AccountId;
+
+use super::{
+    port::{
+        input::{CloseAccountError, CloseAccountUseCase},
+        output::{UnitOfWork, UnitOfWorkPort},
+    },
+    BASELINE_WINDOW_DAYS,
+};
+
+#[derive(Component)]
+#[shaku(interface = CloseAccountUseCase)]
+pub struct CloseAccountService {
+    #[shaku(inject)]
+    unit_of_work_port: Arc<dyn UnitOfWorkPort>,
+}
+
+#[rocket::async_trait]
+impl CloseAccountUseCase for CloseAccountService {
+    async fn close_account(&self, account_id: AccountId) -> Result<(), CloseAccountError> {
+        let mut uow = self.unit_of_work_port.begin().await?;
+
+        match Self::close_within(uow.as_mut(), account_id).await {
+            Ok(()) => Ok(uow.commit().await?),
+            Err(err) => {
+                if let Err(rollback_err) = uow.rollback().await {
+                    log::error!("Unable to roll back account closing: {}", rollback_err);
+                }
+                Err(err)
+            }
+        }
+    }
+}
+
+impl CloseAccountService {
+    async fn close_within(
+        uow: &mut dyn UnitOfWork,
+        account_id: AccountId,
+    ) -> Result<(), CloseAccountError> {
+        let baseline_date = Utc::now() - Duration::days(BASELINE_WINDOW_DAYS);
+
+        // Keep transfers from changing the balance while it is checked
+        uow.lock_accounts(&[account_id]).await?;
+        let mut account = uow.load_account(account_id, baseline_date).await?;
+        account.close()?;
+        uow.update_status(&account).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use rocket::tokio;
+
+    use crate::{
+        application::tests::MockUnitOfWorkPort,
+        domain::{
+            account::{AccountError, AccountStatus},
+            money::tests::eur,
+        },
+    };
+
+    use super::*;
+
+    fn service_with_balance(balance: i64) -> (CloseAccountService, Arc<MockUnitOfWorkPort>) {
+        let uow_port = Arc::new(MockUnitOfWorkPort::with_balances(&[(
+            AccountId(41),
+            eur(balance),
+        )]));
+        let service = CloseAccountService {
+            unit_of_work_port: uow_port.clone(),
+        };
+
+        (service, uow_port)
+    }
+
+    #[tokio::test]
+    async fn it_closes_accounts() -> Result<()> {
+        // Given
+        let (service, uow_port) = service_with_balance(0);
+
+        // When
+        service.close_account(AccountId(41)).await?;
+
+        // Expect
+        assert_eq!(
+            uow_port.state().statuses,
+            vec![(AccountId(41), AccountStatus::Closed)]
+        );
+        assert_eq!(uow_port.state().locked, vec![AccountId(41)]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_non_zero_balance_then_close_account_fails() -> Result<()> {
+        // Given
+        let (service, uow_port) = service_with_balance(10);
+
+        // When
+        let result = service.close_account(AccountId(41)).await;
+
+        // Expect
+        assert!(matches!(
+            result,
+            Err(CloseAccountError::Account(AccountError::NonZeroBalance(_)))
+        ));
+        assert!(uow_port.state().statuses.is_empty());
+        assert_eq!(uow_port.state().rollbacks, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_unknown_account_then_close_account_fails() -> Result<()> {
+        // Given
+        let (service, _) = service_with_balance(0);
+
+        // When
+        let result = service.close_account(AccountId(99)).await;
+
+        // Expect
+        assert!(matches!(
+            result,
+            Err(CloseAccountError::AccountNotFound(AccountId(99)))
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use crate::domain::account::{Account, AccountId};
+
+use super::port::{
+    input::{CreateAccountCommand, CreateAccountError, CreateAccountUseCase},
+    output::{CreateAccountPort, IdGenerator},
+};
+
+#[derive(Component)]
+#[shaku(interface = CreateAccountUseCase)]
+pub struct CreateAccountService {
+    #[shaku(inject)]
+    id_generator: Arc<dyn IdGenerator>,
+    #[shaku(inject)]
+    create_account_port: Arc<dyn CreateAccountPort>,
+}
+
+#[rocket::async_trait]
+impl CreateAccountUseCase for CreateAccountService {
+    async fn create_account(
+        &self,
+        cmd: CreateAccountCommand,
+    ) -> Result<AccountId, CreateAccountError> {
+        let account_id = self.id_generator.next_account_id().await?;
+        let account = Account::open(account_id, cmd.currency(), cmd.overdraft_limit());
+        self.create_account_port.create_account(&account).await?;
+
+        Ok(account_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use rocket::tokio;
+
+    use crate::{
+        application::tests::{MockCreateAccountPort, MockIdGenerator},
+        domain::{
+            account::AccountStatus,
+            money::{Currency, Money},
+        },
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_creates_accounts_with_generated_ids() -> Result<()> {
+        // Given
+        let port = Arc::new(MockCreateAccountPort::default());
+        let service = CreateAccountService {
+            id_generator: Arc::new(MockIdGenerator::default()),
+            create_account_port: port.clone(),
+        };
+
+        // When
+        let first = service
+            .create_account(CreateAccountCommand::try_new(Currency::EUR, None)?)
+            .await?;
+        let second = service
+            .create_account(CreateAccountCommand::try_new(Currency::JPY, None)?)
+            .await?;
+
+        // Expect
+        assert_eq!(first, AccountId(1));
+        assert_eq!(second, AccountId(2));
+        let created = port.created();
+        assert_eq!(created[1].currency(), Currency::JPY);
+        assert_eq!(created[1].status(), AccountStatus::Active);
+        assert_eq!(created[1].calculate_balance()?, Money::zero(Currency::JPY));
+        Ok(())
+    }
+}
@@ -0,0 +1,245 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::domain::scheduled_transfer::ScheduledTransfer;
+
+use super::{
+    money_transfer_properties::MoneyTransferProperties,
+    port::{
+        input::{ExecuteScheduledTransfersUseCase, SendMoneyCommand, SendMoneyUseCase},
+        output::{PersistenceError, ScheduledTransferPort},
+    },
+};
+
+#[derive(Component)]
+#[shaku(interface = ExecuteScheduledTransfersUseCase)]
+pub struct ExecuteScheduledTransfersService {
+    #[shaku(inject)]
+    scheduled_transfer_port: Arc<dyn ScheduledTransferPort>,
+    #[shaku(inject)]
+    send_money_use_case: Arc<dyn SendMoneyUseCase>,
+    #[shaku(inject)]
+    money_transfer_properties: Arc<dyn MoneyTransferProperties>,
+}
+
+#[rocket::async_trait]
+impl ExecuteScheduledTransfersUseCase for ExecuteScheduledTransfersService {
+    async fn execute_due_transfers(
+        &self,
+        now: DateTime<Utc>,
+        claim_timeout: Duration,
+        limit: u32,
+    ) -> Result<usize, PersistenceError> {
+        let transfers = self
+            .scheduled_transfer_port
+            .claim_due(now, claim_timeout, limit)
+            .await?;
+
+        for mut transfer in transfers.iter().cloned() {
+            match self.send(&transfer).await {
+                Ok(()) => transfer.mark_executed(Utc::now()),
+                Err(failure) => {
+                    log::warn!("Scheduled transfer failed: {}", failure);
+                    transfer.mark_failed(Utc::now(), failure)
+                }
+            }
+
+            // Left executing, the transfer is claimed again once its claim expired
+            if let Err(err) = self.scheduled_transfer_port.record_outcome(&transfer).await {
+                log::error!("Unable to record scheduled transfer outcome: {}", err);
+            }
+        }
+
+        Ok(transfers.len())
+    }
+}
+
+impl ExecuteScheduledTransfersService {
+    /// Send the money of `transfer`, describing why it failed otherwise.
+    ///
+    /// The money is sent with a reserved idempotency key identifying the transfer, so that
+    /// executing it again after a claim expired does not send it twice.
+    async fn send(&self, transfer: &ScheduledTransfer) -> Result<(), String> {
+        let id = transfer
+            .id()
+            .ok_or_else(|| "Scheduled transfer id is not set".to_owned())?;
+        let cmd = SendMoneyCommand::try_new(
+            *transfer.source_account_id(),
+            *transfer.target_account_id(),
+            *transfer.money(),
+            self.money_transfer_properties.command_ceiling(),
+        )
+        .map_err(|e| e.to_string())?
+        .for_scheduled_transfer(*id);
+
+        self.send_money_use_case
+            .send_money(cmd)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use parking_lot::Mutex;
+    use rocket::tokio;
+
+    use crate::{
+        application::{
+            port::{input::SendMoneyError, output::IdempotencyKey},
+            tests::MockScheduledTransferPort,
+            MoneyTransferPropertiesImpl,
+        },
+        domain::{
+            account::AccountId,
+            money::tests::eur,
+            scheduled_transfer::{ScheduledTransferId, ScheduledTransferStatus},
+        },
+    };
+
+    use super::*;
+
+    /// Sends money only from account `1`, once per idempotency key.
+    #[derive(Default)]
+    struct MockSendMoneyUseCase {
+        sent: Mutex<Vec<IdempotencyKey>>,
+    }
+
+    #[rocket::async_trait]
+    impl SendMoneyUseCase for MockSendMoneyUseCase {
+        async fn send_money(&self, cmd: SendMoneyCommand) -> Result<(), SendMoneyError> {
+            match cmd.source_account_id() {
+                AccountId(1) => {
+                    let key = cmd.idempotency_key().unwrap();
+                    let mut sent = self.sent.lock();
+                    if !sent.contains(key) {
+                        sent.push(key.clone());
+                    }
+                    Ok(())
+                }
+                id => Err(SendMoneyError::AccountNotFound(*id)),
+            }
+        }
+    }
+
+    fn service_with(
+        transfers: Vec<ScheduledTransfer>,
+    ) -> (
+        ExecuteScheduledTransfersService,
+        Arc<MockScheduledTransferPort>,
+        Arc<MockSendMoneyUseCase>,
+    ) {
+        let port = Arc::new(MockScheduledTransferPort::with_transfers(transfers));
+        let send_money = Arc::new(MockSendMoneyUseCase::default());
+        let service = ExecuteScheduledTransfersService {
+            scheduled_transfer_port: port.clone(),
+            send_money_use_case: send_money.clone(),
+            money_transfer_properties: Arc::new(MoneyTransferPropertiesImpl::new(eur(1000))),
+        };
+
+        (service, port, send_money)
+    }
+
+    #[tokio::test]
+    async fn it_executes_due_transfers_and_records_outcomes() -> Result<()> {
+        // Given
+        let now = Utc::now();
+        let (service, port, send_money) = service_with(vec![
+            ScheduledTransfer::new(AccountId(1), AccountId(2), eur(100), now),
+            ScheduledTransfer::new(AccountId(3), AccountId(2), eur(100), now),
+            ScheduledTransfer::new(
+                AccountId(1),
+                AccountId(2),
+                eur(100),
+                now + Duration::days(1),
+            ),
+        ]);
+
+        // When
+        let executed = service
+            .execute_due_transfers(now, Duration::minutes(10), 10)
+            .await?;
+
+        // Expect
+        assert_eq!(executed, 2);
+        let statuses: Vec<_> = port.transfers().iter().map(|t| t.status()).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ScheduledTransferStatus::Executed,
+                ScheduledTransferStatus::Failed,
+                ScheduledTransferStatus::Pending,
+            ]
+        );
+        assert_eq!(port.transfers()[1].failure(), Some("Account 3 not found"));
+        assert_eq!(
+            *send_money.sent.lock(),
+            vec![IdempotencyKey::scheduled_transfer(ScheduledTransferId(0))]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_unrecorded_outcome_then_other_outcomes_are_recorded() -> Result<()> {
+        // Given
+        let now = Utc::now();
+        let (service, port, _) = service_with(vec![
+            ScheduledTransfer::new(AccountId(1), AccountId(2), eur(100), now),
+            ScheduledTransfer::new(AccountId(1), AccountId(2), eur(200), now),
+        ]);
+        port.fail_outcomes_of(Some(ScheduledTransferId(0)));
+
+        // When
+        let executed = service
+            .execute_due_transfers(now, Duration::minutes(10), 10)
+            .await?;
+
+        // Expect
+        assert_eq!(executed, 2);
+        let statuses: Vec<_> = port.transfers().iter().map(|t| t.status()).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ScheduledTransferStatus::Executing,
+                ScheduledTransferStatus::Executed,
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_expired_claim_then_transfer_is_executed_again_but_sent_once() -> Result<()> {
+        // Given
+        let now = Utc::now();
+        let timeout = Duration::minutes(10);
+        let (service, port, send_money) = service_with(vec![ScheduledTransfer::new(
+            AccountId(1),
+            AccountId(2),
+            eur(100),
+            now,
+        )]);
+        port.fail_outcomes_of(Some(ScheduledTransferId(0)));
+        service.execute_due_transfers(now, timeout, 10).await?;
+        port.fail_outcomes_of(None);
+
+        // When
+        let before_timeout = service
+            .execute_due_transfers(now + timeout / 2, timeout, 10)
+            .await?;
+        let after_timeout = service
+            .execute_due_transfers(now + timeout * 2, timeout, 10)
+            .await?;
+
+        // Expect
+        assert_eq!(before_timeout, 0);
+        assert_eq!(after_timeout, 1);
+        assert_eq!(
+            port.transfers()[0].status(),
+            ScheduledTransferStatus::Executed
+        );
+        assert_eq!(send_money.sent.lock().len(), 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::domain::account::{Account, AccountError, AccountId};
+
+use super::{
+    port::{
+        input::{AccountBalance, GetAccountBalanceError, GetAccountBalanceQuery},
+        output::LoadAccountPort,
+    },
+    BASELINE_WINDOW_DAYS,
+};
+
+#[derive(Component)]
+#[shaku(interface = GetAccountBalanceQuery)]
+pub struct GetAccountBalanceService {
+    #[shaku(inject)]
+    load_account_port: Arc<dyn LoadAccountPort>,
+}
+
+#[rocket::async_trait]
+impl GetAccountBalanceQuery for GetAccountBalanceService {
+    async fn get_account_balance(
+        &self,
+        account_id: AccountId,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<AccountBalance, GetAccountBalanceError> {
+        let since = since.unwrap_or_else(|| Utc::now() - Duration::days(BASELINE_WINDOW_DAYS));
+        let account = self
+            .load_account_port
+            .load_account(account_id, since)
+            .await?;
+
+        Ok(account_balance(&account, since)?)
+    }
+}
+
+/// Report the balance of `account`, whose activity window starts at `since`.
+pub(super) fn account_balance(
+    account: &Account,
+    since: DateTime<Utc>,
+) -> Result<AccountBalance, AccountError> {
+    let account_id = *account.id().ok_or(AccountError::MissingAccountId)?;
+    let balance = account.calculate_balance()?;
+
+    Ok(AccountBalance::new(
+        account_id,
+        since,
+        *account.baseline_balance(),
+        balance,
+        account.activity_window().activities().to_vec(),
+    )
+    .with_overdraft_limit(account.overdraft_limit())
+    .with_status(account.status()))
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use rocket::tokio;
+
+    use crate::{
+        domain::{
+            account::tests::default_account,
+            money::{tests::eur, MoneyError},
+        },
+        infrastructure::container::tests::MockLoadAccountPort,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_gets_account_balance() -> Result<()> {
+        // Given
+        let service = GetAccountBalanceService {
+            load_account_port: Arc::new(MockLoadAccountPort()),
+        };
+        let since = Utc::now();
+
+        // When
+        let balance = service
+            .get_account_balance(AccountId(43), Some(since))
+            .await?;
+
+        // Expect
+        assert_eq!(*balance.account_id(), AccountId(43));
+        assert_eq!(*balance.since(), since);
+        assert_eq!(*balance.baseline_balance(), eur(999));
+        assert_eq!(*balance.balance(), eur(999));
+        assert_eq!(balance.overdraft_limit(), None);
+        assert_eq!(balance.activities().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn given_overflowing_balance_then_account_error_is_reported() {
+        // Given
+        let account = default_account()
+            .baseline_balance(eur(i64::MIN))
+            .build()
+            .unwrap();
+
+        // When
+        let result = account_balance(&account, Utc::now());
+
+        // Expect
+        assert!(matches!(
+            result,
+            Err(AccountError::Money(MoneyError::Overflow(_)))
+        ));
+    }
+}
@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+
+use crate::domain::customer::CustomerId;
+
+use super::{
+    get_account_balance_service::account_balance,
+    port::{
+        input::{CustomerAccounts, GetCustomerAccountsError, GetCustomerAccountsQuery},
+        output::{LoadAccountPort, LoadCustomerPort},
+    },
+    BASELINE_WINDOW_DAYS,
+};
+
+#[derive(Component)]
+#[shaku(interface = GetCustomerAccountsQuery)]
+pub struct GetCustomerAccountsService {
+    #[shaku(inject)]
+    load_customer_port: Arc<dyn LoadCustomerPort>,
+    #[shaku(inject)]
+    load_account_port: Arc<dyn LoadAccountPort>,
+}
+
+#[rocket::async_trait]
+impl GetCustomerAccountsQuery for GetCustomerAccountsService {
+    async fn get_customer_accounts(
+        &self,
+        customer_id: CustomerId,
+    ) -> Result<CustomerAccounts, GetCustomerAccountsError> {
+        let since = Utc::now() - Duration::days(BASELINE_WINDOW_DAYS);
+        let customer = self.load_customer_port.load_customer(customer_id).await?;
+
+        let mut accounts = Vec::with_capacity(customer.account_ids().len());
+        for account_id in customer.account_ids() {
+            let account = self
+                .load_account_port
+                .load_account(*account_id, since)
+                .await?;
+            accounts.push(account_balance(&account, since)?);
+        }
+
+        Ok(CustomerAccounts::new(customer, accounts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use rocket::tokio;
+
+    use crate::{
+        application::port::output::PersistenceError,
+        domain::{account::AccountId, customer::Customer, money::tests::eur},
+        infrastructure::container::tests::MockLoadAccountPort,
+    };
+
+    use super::*;
+
+    /// Knows only customer `1`, owning accounts `41` and `42`.
+    struct MockLoadCustomerPort;
+
+    #[rocket::async_trait]
+    impl LoadCustomerPort for MockLoadCustomerPort {
+        async fn load_customer(
+            &self,
+            customer_id: CustomerId,
+        ) -> Result<Customer, PersistenceError> {
+            if customer_id != CustomerId(1) {
+                return Err(PersistenceError::CustomerNotFound(customer_id));
+            }
+
+            Ok(Customer::new(
+                customer_id,
+                "Ada Lovelace".into(),
+                vec![AccountId(41), AccountId(42)],
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn it_gets_customer_accounts() -> Result<()> {
+        // Given
+        let service = GetCustomerAccountsService {
+            load_customer_port: Arc::new(MockLoadCustomerPort),
+            load_account_port: Arc::new(MockLoadAccountPort()),
+        };
+
+        // When
+        let accounts = service.get_customer_accounts(CustomerId(1)).await?;
+        let unknown = service.get_customer_accounts(CustomerId(2)).await;
+
+        // Expect
+        assert_eq!(accounts.customer().name(), "Ada Lovelace");
+        assert_eq!(accounts.accounts().len(), 2);
+        assert_eq!(*accounts.accounts()[0].balance(), eur(999));
+        assert!(matches!(
+            unknown,
+            Err(GetCustomerAccountsError::CustomerNotFound(CustomerId(2)))
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use crate::domain::ledger::LedgerAudit;
+
+use super::port::{
+    input::{LedgerAuditError, LedgerAuditUseCase},
+    output::LoadActivityPort,
+};
+
+#[derive(Component)]
+#[shaku(interface = LedgerAuditUseCase)]
+pub struct LedgerAuditService {
+    #[shaku(inject)]
+    load_activity_port: Arc<dyn LoadActivityPort>,
+}
+
+#[rocket::async_trait]
+impl LedgerAuditUseCase for LedgerAuditService {
+    async fn audit_ledger(&self) -> Result<LedgerAudit, LedgerAuditError> {
+        let activities = self.load_activity_port.load_activities().await?;
+
+        Ok(LedgerAudit::of(activities)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use rocket::tokio;
+
+    use crate::{
+        application::tests::MockLoadActivityPort,
+        domain::{account::AccountId, activity::tests::default_activity, money::tests::eur},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_audits_every_activity() -> Result<()> {
+        // Given
+        let transfer = default_activity()
+            .source_account_id(AccountId(1))
+            .target_account_id(AccountId(2))
+            .money(eur(500))
+            .clone();
+        let service = LedgerAuditService {
+            load_activity_port: Arc::new(MockLoadActivityPort::with_activities(vec![
+                transfer.clone().owner_account_id(AccountId(1)).build()?,
+                transfer.clone().owner_account_id(AccountId(2)).build()?,
+                transfer.clone().owner_account_id(AccountId(1)).build()?,
+            ])),
+        };
+
+        // When
+        let audit = service.audit_ledger().await?;
+
+        // Expect
+        assert_eq!(audit.activity_count(), 3);
+        assert_eq!(audit.pair_count(), 1);
+        assert_eq!(audit.orphans().len(), 1);
+        assert_eq!(audit.imbalances(), &[eur(-500)]);
+        Ok(())
+    }
+}
@@ -1,8 +1,37 @@
+mod close_account_service;
+mod create_account_service;
+mod execute_scheduled_transfers_service;
+mod get_account_balance_service;
+mod get_customer_accounts_service;
+mod ledger_audit_service;
+mod money_transfer_properties;
+mod open_account_service;
 pub mod port;
+mod reverse_transfer_service;
+mod schedule_transfer_service;
+mod send_money_batch_service;
 mod send_money_service;
 
+#[cfg(test)]
+pub mod tests;
+
+pub use close_account_service::*;
+pub use create_account_service::*;
+pub use execute_scheduled_transfers_service::*;
+pub use get_account_balance_service::*;
+pub use get_customer_accounts_service::*;
+pub use ledger_audit_service::*;
+pub use money_transfer_properties::*;
+pub use open_account_service::*;
+pub use reverse_transfer_service::*;
+pub use schedule_transfer_service::*;
+pub use send_money_batch_service::*;
 pub use send_money_service::*;
 
+/// Number of days of activities loaded into an account's activity window.
+/// Older activities are folded into the account's baseline balance.
+const BASELINE_WINDOW_DAYS: i64 = 10;
+
 use port::input::HelloWorldUseCase;
 use port::input::PingPongUseCase;
 
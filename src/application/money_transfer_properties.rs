@@ -0,0 +1,87 @@
+use chrono::Duration;
+use shaku::Interface;
+
+use crate::{
+    application::port::input::SendMoneyCommand,
+    domain::{
+        account::AccountId,
+        fee::{FeePolicy, NoFee},
+        money::{Currency, Money},
+    },
+};
+
+/// Configuration properties of money transfer use cases.
+pub trait MoneyTransferProperties: Interface {
+    /// Maximum amount of money that can be transferred at once.
+    fn maximum_transfer_threshold(&self) -> Money;
+
+    /// Maximum amount of a single command, in minor units of its currency.
+    fn command_ceiling(&self) -> i64;
+
+    /// Policy computing the fee charged for each transfer.
+    fn fee_policy(&self) -> &dyn FeePolicy;
+
+    /// Account collecting transfer fees. No fee is charged when unset.
+    fn fee_account_id(&self) -> Option<AccountId>;
+
+    /// How long a transfer sent with an idempotency key is remembered.
+    fn idempotency_retention(&self) -> Duration;
+}
+
+#[derive(Component)]
+#[shaku(interface = MoneyTransferProperties)]
+pub struct MoneyTransferPropertiesImpl {
+    #[shaku(default = Money::new(1_000_000, Currency::EUR))]
+    maximum_transfer_threshold: Money,
+    #[shaku(default = SendMoneyCommand::DEFAULT_CEILING)]
+    command_ceiling: i64,
+    #[shaku(default = Box::new(NoFee))]
+    fee_policy: Box<dyn FeePolicy>,
+    #[shaku(default)]
+    fee_account_id: Option<AccountId>,
+    #[shaku(default = Duration::hours(24))]
+    idempotency_retention: Duration,
+}
+
+impl MoneyTransferPropertiesImpl {
+    pub fn new(maximum_transfer_threshold: Money) -> Self {
+        Self {
+            maximum_transfer_threshold,
+            command_ceiling: SendMoneyCommand::DEFAULT_CEILING,
+            fee_policy: Box::new(NoFee),
+            fee_account_id: None,
+            idempotency_retention: Duration::hours(24),
+        }
+    }
+
+    /// Charge fees according to `fee_policy`, collected by `fee_account_id`.
+    pub fn with_fees(self, fee_policy: Box<dyn FeePolicy>, fee_account_id: AccountId) -> Self {
+        Self {
+            fee_policy,
+            fee_account_id: Some(fee_account_id),
+            ..self
+        }
+    }
+}
+
+impl MoneyTransferProperties for MoneyTransferPropertiesImpl {
+    fn maximum_transfer_threshold(&self) -> Money {
+        self.maximum_transfer_threshold
+    }
+
+    fn command_ceiling(&self) -> i64 {
+        self.command_ceiling
+    }
+
+    fn fee_policy(&self) -> &dyn FeePolicy {
+        self.fee_policy.as_ref()
+    }
+
+    fn fee_account_id(&self) -> Option<AccountId> {
+        self.fee_account_id
+    }
+
+    fn idempotency_retention(&self) -> Duration {
+        self.idempotency_retention
+    }
+}
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use crate::domain::account::Account;
+
+use super::port::{
+    input::{OpenAccountCommand, OpenAccountError, OpenAccountUseCase},
+    output::CreateAccountPort,
+};
+
+#[derive(Component)]
+#[shaku(interface = OpenAccountUseCase)]
+pub struct OpenAccountService {
+    #[shaku(inject)]
+    create_account_port: Arc<dyn CreateAccountPort>,
+}
+
+#[rocket::async_trait]
+impl OpenAccountUseCase for OpenAccountService {
+    async fn open_account(&self, cmd: OpenAccountCommand) -> Result<(), OpenAccountError> {
+        let account = Account::open(*cmd.account_id(), cmd.currency(), cmd.overdraft_limit());
+
+        Ok(self.create_account_port.create_account(&account).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use rocket::tokio;
+
+    use crate::{
+        application::tests::MockCreateAccountPort,
+        domain::{
+            account::AccountId,
+            money::{tests::eur, Currency},
+        },
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_opens_accounts() -> Result<()> {
+        // Given
+        let port = Arc::new(MockCreateAccountPort::default());
+        let service = OpenAccountService {
+            create_account_port: port.clone(),
+        };
+        let cmd = OpenAccountCommand::try_new(AccountId(7), Currency::EUR, Some(eur(100)))?;
+
+        // When
+        service.open_account(cmd).await?;
+
+        // Expect
+        let created = port.created();
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].id(), Some(&AccountId(7)));
+        assert_eq!(created[0].overdraft_limit(), Some(eur(100)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_existing_account_then_open_account_fails() -> Result<()> {
+        // Given
+        let port = Arc::new(MockCreateAccountPort::with_existing(&[AccountId(7)]));
+        let service = OpenAccountService {
+            create_account_port: port.clone(),
+        };
+        let cmd = OpenAccountCommand::try_new(AccountId(7), Currency::EUR, None)?;
+
+        // When
+        let result = service.open_account(cmd).await;
+
+        // Expect
+        assert!(matches!(
+            result,
+            Err(OpenAccountError::AccountAlreadyExists(AccountId(7)))
+        ));
+        assert!(port.created().is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use derive_more::{Display, Error, From};
+use shaku::Interface;
+
+use crate::{
+    application::port::output::PersistenceError,
+    domain::account::{AccountError, AccountId},
+};
+
+#[rocket::async_trait]
+pub trait CloseAccountUseCase: Interface {
+    /// Close an account for good. Its balance must be zero.
+    async fn close_account(&self, account_id: AccountId) -> Result<(), CloseAccountError>;
+}
+
+#[derive(Debug, Display, Error, From)]
+pub enum CloseAccountError {
+    #[display(fmt = "Account {} not found", "_0.0")]
+    #[from(ignore)]
+    AccountNotFound(#[error(not(source))] AccountId),
+    #[display(fmt = "{}", _0)]
+    Account(AccountError),
+    #[display(fmt = "{}", _0)]
+    #[from(ignore)]
+    Persistence(PersistenceError),
+}
+
+impl From<PersistenceError> for CloseAccountError {
+    fn from(err: PersistenceError) -> Self {
+        match err {
+            PersistenceError::AccountNotFound(id) => CloseAccountError::AccountNotFound(id),
+            err => CloseAccountError::Persistence(err),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use derive_more::{Display, Error, From};
+use shaku::Interface;
+
+use super::{open_account_usecase::check_overdraft_limit, ValidationError, Validator};
+use crate::{
+    application::port::output::PersistenceError,
+    domain::{
+        account::AccountId,
+        money::{Currency, Money},
+    },
+};
+
+#[rocket::async_trait]
+pub trait CreateAccountUseCase: Interface {
+    /// Open a new, active account with a zero balance and a generated id.
+    async fn create_account(
+        &self,
+        cmd: CreateAccountCommand,
+    ) -> Result<AccountId, CreateAccountError>;
+}
+
+#[derive(Debug, Display, Error, From)]
+pub enum CreateAccountError {
+    /// The generated id was taken concurrently by an account opened with an explicit id.
+    #[display(fmt = "Account {} already exists", "_0.0")]
+    #[from(ignore)]
+    AccountAlreadyExists(#[error(not(source))] AccountId),
+    #[display(fmt = "{}", _0)]
+    #[from(ignore)]
+    Persistence(PersistenceError),
+}
+
+impl From<PersistenceError> for CreateAccountError {
+    fn from(err: PersistenceError) -> Self {
+        match err {
+            PersistenceError::AccountAlreadyExists(id) => {
+                CreateAccountError::AccountAlreadyExists(id)
+            }
+            err => CreateAccountError::Persistence(err),
+        }
+    }
+}
+
+pub struct CreateAccountCommand {
+    currency: Currency,
+    overdraft_limit: Option<Money>,
+}
+
+impl CreateAccountCommand {
+    pub fn try_new(
+        currency: Currency,
+        overdraft_limit: Option<Money>,
+    ) -> Result<Self, ValidationError> {
+        let mut validator = Validator::default();
+        check_overdraft_limit(&mut validator, currency, overdraft_limit);
+        validator.finish()?;
+
+        Ok(Self {
+            currency,
+            overdraft_limit,
+        })
+    }
+
+    /// Get the create account command's currency.
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Get the create account command's overdraft limit.
+    pub fn overdraft_limit(&self) -> Option<Money> {
+        self.overdraft_limit
+    }
+}
@@ -0,0 +1,125 @@
+use chrono::{DateTime, Utc};
+use derive_more::{Display, Error, From};
+use shaku::Interface;
+
+use crate::{
+    application::port::output::PersistenceError,
+    domain::{
+        account::{AccountError, AccountId, AccountStatus},
+        activity::Activity,
+        money::Money,
+    },
+};
+
+#[rocket::async_trait]
+pub trait GetAccountBalanceQuery: Interface {
+    /// Get the balance of an account, along with the activities recorded since `since`.
+    ///
+    /// When `since` is `None`, the default activity window is used.
+    async fn get_account_balance(
+        &self,
+        account_id: AccountId,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<AccountBalance, GetAccountBalanceError>;
+}
+
+#[derive(Debug, Display, Error, From)]
+pub enum GetAccountBalanceError {
+    #[display(fmt = "Account {} not found", "_0.0")]
+    #[from(ignore)]
+    AccountNotFound(#[error(not(source))] AccountId),
+    /// The balance of the loaded account cannot be calculated.
+    #[display(fmt = "{}", _0)]
+    Account(AccountError),
+    #[display(fmt = "{}", _0)]
+    #[from(ignore)]
+    Persistence(PersistenceError),
+}
+
+impl From<PersistenceError> for GetAccountBalanceError {
+    fn from(err: PersistenceError) -> Self {
+        match err {
+            PersistenceError::AccountNotFound(id) => GetAccountBalanceError::AccountNotFound(id),
+            err => GetAccountBalanceError::Persistence(err),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AccountBalance {
+    account_id: AccountId,
+    since: DateTime<Utc>,
+    baseline_balance: Money,
+    balance: Money,
+    overdraft_limit: Option<Money>,
+    status: AccountStatus,
+    activities: Vec<Activity>,
+}
+
+impl AccountBalance {
+    pub fn new(
+        account_id: AccountId,
+        since: DateTime<Utc>,
+        baseline_balance: Money,
+        balance: Money,
+        activities: Vec<Activity>,
+    ) -> Self {
+        Self {
+            account_id,
+            since,
+            baseline_balance,
+            balance,
+            overdraft_limit: None,
+            status: AccountStatus::Active,
+            activities,
+        }
+    }
+
+    /// Report the account's overdraft limit along with its balance.
+    pub fn with_overdraft_limit(self, overdraft_limit: Option<Money>) -> Self {
+        Self {
+            overdraft_limit,
+            ..self
+        }
+    }
+
+    /// Get a reference to the account balance's account id.
+    pub fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    /// Get a reference to the start of the account balance's activity window.
+    pub fn since(&self) -> &DateTime<Utc> {
+        &self.since
+    }
+
+    /// Get a reference to the account balance's baseline balance.
+    pub fn baseline_balance(&self) -> &Money {
+        &self.baseline_balance
+    }
+
+    /// Get a reference to the account balance's current balance.
+    pub fn balance(&self) -> &Money {
+        &self.balance
+    }
+
+    /// Report the account's status along with its balance.
+    pub fn with_status(self, status: AccountStatus) -> Self {
+        Self { status, ..self }
+    }
+
+    /// Get the account balance's account status.
+    pub fn status(&self) -> AccountStatus {
+        self.status
+    }
+
+    /// Get the account balance's overdraft limit.
+    pub fn overdraft_limit(&self) -> Option<Money> {
+        self.overdraft_limit
+    }
+
+    /// Get a reference to the account balance's activities.
+    pub fn activities(&self) -> &[Activity] {
+        self.activities.as_slice()
+    }
+}
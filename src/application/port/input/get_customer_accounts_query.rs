@@ -0,0 +1,71 @@
+use derive_more::{Display, Error, From};
+use shaku::Interface;
+
+use crate::{
+    application::port::output::PersistenceError,
+    domain::{
+        account::{AccountError, AccountId},
+        customer::{Customer, CustomerId},
+    },
+};
+
+use super::AccountBalance;
+
+#[rocket::async_trait]
+pub trait GetCustomerAccountsQuery: Interface {
+    /// Get a customer along with the balance of each of their accounts.
+    async fn get_customer_accounts(
+        &self,
+        customer_id: CustomerId,
+    ) -> Result<CustomerAccounts, GetCustomerAccountsError>;
+}
+
+#[derive(Debug, Display, Error, From)]
+pub enum GetCustomerAccountsError {
+    #[display(fmt = "Customer {} not found", "_0.0")]
+    #[from(ignore)]
+    CustomerNotFound(#[error(not(source))] CustomerId),
+    #[display(fmt = "Account {} not found", "_0.0")]
+    #[from(ignore)]
+    AccountNotFound(#[error(not(source))] AccountId),
+    /// The balance of one of the customer's accounts cannot be calculated.
+    #[display(fmt = "{}", _0)]
+    Account(AccountError),
+    #[display(fmt = "{}", _0)]
+    #[from(ignore)]
+    Persistence(PersistenceError),
+}
+
+impl From<PersistenceError> for GetCustomerAccountsError {
+    fn from(err: PersistenceError) -> Self {
+        match err {
+            PersistenceError::CustomerNotFound(id) => {
+                GetCustomerAccountsError::CustomerNotFound(id)
+            }
+            PersistenceError::AccountNotFound(id) => GetCustomerAccountsError::AccountNotFound(id),
+            err => GetCustomerAccountsError::Persistence(err),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CustomerAccounts {
+    customer: Customer,
+    accounts: Vec<AccountBalance>,
+}
+
+impl CustomerAccounts {
+    pub fn new(customer: Customer, accounts: Vec<AccountBalance>) -> Self {
+        Self { customer, accounts }
+    }
+
+    /// Get a reference to the customer.
+    pub fn customer(&self) -> &Customer {
+        &self.customer
+    }
+
+    /// Get a reference to the balances of the customer's accounts.
+    pub fn accounts(&self) -> &[AccountBalance] {
+        self.accounts.as_slice()
+    }
+}
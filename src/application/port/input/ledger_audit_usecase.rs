@@ -0,0 +1,22 @@
+use derive_more::{Display, Error, From};
+use shaku::Interface;
+
+use crate::{
+    application::port::output::PersistenceError,
+    domain::{ledger::LedgerAudit, money::MoneyError},
+};
+
+#[rocket::async_trait]
+pub trait LedgerAuditUseCase: Interface {
+    /// Check that every activity of the ledger is mirrored by the other account of its
+    /// transfer, and that the ledger sums to zero.
+    async fn audit_ledger(&self) -> Result<LedgerAudit, LedgerAuditError>;
+}
+
+#[derive(Debug, Display, Error, From)]
+pub enum LedgerAuditError {
+    #[display(fmt = "{}", _0)]
+    Money(MoneyError),
+    #[display(fmt = "{}", _0)]
+    Persistence(PersistenceError),
+}
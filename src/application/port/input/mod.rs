@@ -1,6 +1,26 @@
+mod close_account_usecase;
+mod create_account_usecase;
+mod get_account_balance_query;
+mod get_customer_accounts_query;
+mod ledger_audit_usecase;
+mod open_account_usecase;
+mod reverse_transfer_usecase;
+mod schedule_transfer_usecase;
+mod send_money_batch_usecase;
 mod send_money_usecase;
+mod validation;
 
+pub use close_account_usecase::*;
+pub use create_account_usecase::*;
+pub use get_account_balance_query::*;
+pub use get_customer_accounts_query::*;
+pub use ledger_audit_usecase::*;
+pub use open_account_usecase::*;
+pub use reverse_transfer_usecase::*;
+pub use schedule_transfer_usecase::*;
+pub use send_money_batch_usecase::*;
 pub use send_money_usecase::*;
+pub use validation::*;
 
 use shaku::Interface;
 
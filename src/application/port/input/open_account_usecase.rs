@@ -0,0 +1,118 @@
+use derive_more::{Display, Error, From};
+use shaku::Interface;
+
+use super::{ValidationError, Validator};
+use crate::{
+    application::port::output::PersistenceError,
+    domain::{
+        account::AccountId,
+        money::{Currency, Money},
+    },
+};
+
+#[rocket::async_trait]
+pub trait OpenAccountUseCase: Interface {
+    /// Open a new, active account with a zero balance.
+    async fn open_account(&self, cmd: OpenAccountCommand) -> Result<(), OpenAccountError>;
+}
+
+#[derive(Debug, Display, Error, From)]
+pub enum OpenAccountError {
+    #[display(fmt = "Account {} already exists", "_0.0")]
+    #[from(ignore)]
+    AccountAlreadyExists(#[error(not(source))] AccountId),
+    #[display(fmt = "{}", _0)]
+    #[from(ignore)]
+    Persistence(PersistenceError),
+}
+
+impl From<PersistenceError> for OpenAccountError {
+    fn from(err: PersistenceError) -> Self {
+        match err {
+            PersistenceError::AccountAlreadyExists(id) => {
+                OpenAccountError::AccountAlreadyExists(id)
+            }
+            err => OpenAccountError::Persistence(err),
+        }
+    }
+}
+
+pub struct OpenAccountCommand {
+    account_id: AccountId,
+    currency: Currency,
+    overdraft_limit: Option<Money>,
+}
+
+impl OpenAccountCommand {
+    pub fn try_new(
+        account_id: AccountId,
+        currency: Currency,
+        overdraft_limit: Option<Money>,
+    ) -> Result<Self, ValidationError> {
+        let mut validator = Validator::default();
+        check_overdraft_limit(&mut validator, currency, overdraft_limit);
+        validator.finish()?;
+
+        Ok(Self {
+            account_id,
+            currency,
+            overdraft_limit,
+        })
+    }
+
+    /// Get a reference to the open account command's account id.
+    pub fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    /// Get the open account command's currency.
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Get the open account command's overdraft limit.
+    pub fn overdraft_limit(&self) -> Option<Money> {
+        self.overdraft_limit
+    }
+}
+
+/// Check that `overdraft_limit`, if any, is a non-negative amount of `currency`.
+pub(super) fn check_overdraft_limit(
+    validator: &mut Validator,
+    currency: Currency,
+    overdraft_limit: Option<Money>,
+) {
+    if let Some(overdraft_limit) = overdraft_limit {
+        validator.check(
+            overdraft_limit.is_positive_or_zero(),
+            "overdraft_limit",
+            "must not be negative",
+        );
+        validator.check(
+            overdraft_limit.currency() == currency,
+            "overdraft_limit",
+            format!("must be in {}", currency),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::money::tests::eur;
+
+    use super::*;
+
+    #[test]
+    fn it_rejects_invalid_overdraft_limits() {
+        for overdraft_limit in [eur(-1), Money::new(100, Currency::USD)] {
+            // When
+            let err =
+                OpenAccountCommand::try_new(AccountId(1), Currency::EUR, Some(overdraft_limit))
+                    .err()
+                    .unwrap();
+
+            // Expect
+            assert_eq!(err.violations()[0].field(), "overdraft_limit");
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use derive_more::{Display, Error, From};
+use shaku::Interface;
+
+use crate::{
+    application::port::output::PersistenceError,
+    domain::{
+        account::AccountError,
+        activity::{Activity, ActivityId},
+    },
+};
+
+#[rocket::async_trait]
+pub trait ReverseTransferUseCase: Interface {
+    /// Undo the transfer recorded by `activity_id`, returning the compensating activities of
+    /// both accounts.
+    async fn reverse_transfer(
+        &self,
+        activity_id: ActivityId,
+    ) -> Result<Vec<Activity>, ReverseTransferError>;
+}
+
+#[derive(Debug, Display, Error, From)]
+pub enum ReverseTransferError {
+    #[display(fmt = "Activity {} not found", "_0.0")]
+    #[from(ignore)]
+    ActivityNotFound(#[error(not(source))] ActivityId),
+    #[display(
+        fmt = "Activity {} was already reversed by activity {}",
+        "original.0",
+        "reversal.0"
+    )]
+    #[from(ignore)]
+    AlreadyReversed {
+        original: ActivityId,
+        reversal: ActivityId,
+    },
+    #[display(
+        fmt = "Activity {} reverses another activity and cannot be reversed",
+        "_0.0"
+    )]
+    #[from(ignore)]
+    ReversalNotReversible(#[error(not(source))] ActivityId),
+    #[display(
+        fmt = "Activity {} is not linked to the other activity of its transfer and cannot be reversed",
+        "_0.0"
+    )]
+    #[from(ignore)]
+    UnlinkedActivity(#[error(not(source))] ActivityId),
+    #[display(fmt = "{}", _0)]
+    Account(AccountError),
+    #[display(fmt = "{}", _0)]
+    #[from(ignore)]
+    Persistence(PersistenceError),
+}
+
+impl From<PersistenceError> for ReverseTransferError {
+    fn from(err: PersistenceError) -> Self {
+        match err {
+            PersistenceError::ActivityNotFound(id) => ReverseTransferError::ActivityNotFound(id),
+            err => ReverseTransferError::Persistence(err),
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use chrono::{DateTime, Duration, Utc};
+use derive_more::{Display, Error, From};
+use shaku::Interface;
+
+use super::{SendMoneyCommand, ValidationError, Validator};
+use crate::{
+    application::port::output::PersistenceError,
+    domain::{
+        account::AccountId,
+        money::Money,
+        scheduled_transfer::{ScheduledTransfer, ScheduledTransferId},
+    },
+};
+
+#[rocket::async_trait]
+pub trait ScheduleTransferUseCase: Interface {
+    /// Schedule a transfer between existing accounts, sent once its execution time has passed.
+    async fn schedule_transfer(
+        &self,
+        cmd: ScheduleTransferCommand,
+    ) -> Result<ScheduledTransferId, ScheduleTransferError>;
+}
+
+#[derive(Debug, Display, Error, From)]
+pub enum ScheduleTransferError {
+    #[display(fmt = "Account {} not found", "_0.0")]
+    #[from(ignore)]
+    AccountNotFound(#[error(not(source))] AccountId),
+    #[display(fmt = "{}", _0)]
+    #[from(ignore)]
+    Persistence(PersistenceError),
+}
+
+impl From<PersistenceError> for ScheduleTransferError {
+    fn from(err: PersistenceError) -> Self {
+        match err {
+            PersistenceError::AccountNotFound(id) => ScheduleTransferError::AccountNotFound(id),
+            err => ScheduleTransferError::Persistence(err),
+        }
+    }
+}
+
+#[rocket::async_trait]
+pub trait ExecuteScheduledTransfersUseCase: Interface {
+    /// Send up to `limit` transfers due at `now`, recording whether each succeeded, and return
+    /// how many were processed.
+    ///
+    /// Transfers whose previous execution did not record an outcome within `claim_timeout` are
+    /// sent again: the money is sent at most once nonetheless.
+    async fn execute_due_transfers(
+        &self,
+        now: DateTime<Utc>,
+        claim_timeout: Duration,
+        limit: u32,
+    ) -> Result<usize, PersistenceError>;
+}
+
+pub struct ScheduleTransferCommand {
+    transfer: SendMoneyCommand,
+    execute_at: DateTime<Utc>,
+}
+
+impl ScheduleTransferCommand {
+    pub fn try_new(
+        source_account_id: AccountId,
+        target_account_id: AccountId,
+        money: Money,
+        execute_at: DateTime<Utc>,
+        ceiling: i64,
+    ) -> Result<Self, ValidationError> {
+        let mut validator = Validator::default();
+        let transfer = validator.include(SendMoneyCommand::try_new(
+            source_account_id,
+            target_account_id,
+            money,
+            ceiling,
+        ));
+        validator.check(
+            execute_at > Utc::now(),
+            "execute_at",
+            "must be in the future",
+        );
+        validator.finish()?;
+
+        Ok(Self {
+            transfer: transfer.expect("transfer violations are reported by the validator"),
+            execute_at,
+        })
+    }
+
+    /// Get a reference to the transfer to execute.
+    pub fn transfer(&self) -> &SendMoneyCommand {
+        &self.transfer
+    }
+
+    /// Get a reference to the time after which the transfer is executed.
+    pub fn execute_at(&self) -> &DateTime<Utc> {
+        &self.execute_at
+    }
+
+    /// Get the pending scheduled transfer described by the command.
+    pub fn to_scheduled_transfer(&self) -> ScheduledTransfer {
+        ScheduledTransfer::new(
+            *self.transfer.source_account_id(),
+            *self.transfer.target_account_id(),
+            *self.transfer.money(),
+            self.execute_at,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use crate::{application::port::input::FieldViolation, domain::money::tests::eur};
+
+    use super::*;
+
+    #[test]
+    fn it_lists_every_violated_field() {
+        // When
+        let err = ScheduleTransferCommand::try_new(
+            AccountId(1),
+            AccountId(2),
+            eur(0),
+            Utc::now() - Duration::hours(1),
+            SendMoneyCommand::DEFAULT_CEILING,
+        )
+        .err()
+        .unwrap();
+
+        // Expect
+        let fields: Vec<_> = err.violations().iter().map(FieldViolation::field).collect();
+        assert_eq!(fields, vec!["money", "execute_at"]);
+    }
+}
@@ -0,0 +1,217 @@
+use shaku::Interface;
+
+use super::{SendMoneyCommand, SendMoneyError, ValidationError, Validator};
+use crate::domain::{account::AccountId, money::Money};
+
+#[rocket::async_trait]
+pub trait SendMoneyBatchUseCase: Interface {
+    /// Send money from one account to many, committing every leg or none.
+    ///
+    /// Failures of single legs are reported in the outcome, the whole batch being rolled back.
+    async fn send_money_batch(
+        &self,
+        cmd: SendMoneyBatchCommand,
+    ) -> Result<BatchTransferOutcome, SendMoneyError>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferLeg {
+    target_account_id: AccountId,
+    money: Money,
+}
+
+impl TransferLeg {
+    /// Get a reference to the transfer leg's target account id.
+    pub fn target_account_id(&self) -> &AccountId {
+        &self.target_account_id
+    }
+
+    /// Get a reference to the transfer leg's money.
+    pub fn money(&self) -> &Money {
+        &self.money
+    }
+}
+
+pub struct SendMoneyBatchCommand {
+    source_account_id: AccountId,
+    legs: Vec<TransferLeg>,
+}
+
+impl SendMoneyBatchCommand {
+    /// Maximum number of legs of a single batch.
+    pub const MAX_LEGS: usize = 100;
+
+    /// Build a command sending each `(target_account_id, money)` leg from `source_account_id`.
+    ///
+    /// Every leg must be a valid `SendMoneyCommand` within `ceiling`, and all legs must share a
+    /// single currency.
+    pub fn try_new(
+        source_account_id: AccountId,
+        legs: Vec<(AccountId, Money)>,
+        ceiling: i64,
+    ) -> Result<Self, ValidationError> {
+        let mut validator = Validator::default();
+        validator.check(!legs.is_empty(), "legs", "must not be empty");
+        validator.check(
+            legs.len() <= Self::MAX_LEGS,
+            "legs",
+            format!("must not exceed {} legs", Self::MAX_LEGS),
+        );
+        validator.check(
+            legs.windows(2)
+                .all(|pair| pair[0].1.currency() == pair[1].1.currency()),
+            "legs",
+            "must share a single currency",
+        );
+
+        let legs: Vec<TransferLeg> = legs
+            .into_iter()
+            .filter_map(|(target_account_id, money)| {
+                validator
+                    .include(SendMoneyCommand::try_new(
+                        source_account_id,
+                        target_account_id,
+                        money,
+                        ceiling,
+                    ))
+                    .map(|_| TransferLeg {
+                        target_account_id,
+                        money,
+                    })
+            })
+            .collect();
+        validator.finish()?;
+
+        Ok(Self {
+            source_account_id,
+            legs,
+        })
+    }
+
+    /// Get a reference to the batch command's source account id.
+    pub fn source_account_id(&self) -> &AccountId {
+        &self.source_account_id
+    }
+
+    /// Get a reference to the batch command's legs.
+    pub fn legs(&self) -> &[TransferLeg] {
+        self.legs.as_slice()
+    }
+}
+
+#[derive(Debug)]
+pub enum LegStatus {
+    Completed,
+    /// The leg was valid, but the batch was rolled back because of another leg.
+    RolledBack,
+    Failed(SendMoneyError),
+}
+
+#[derive(Debug)]
+pub struct LegOutcome {
+    leg: TransferLeg,
+    status: LegStatus,
+}
+
+impl LegOutcome {
+    pub fn new(leg: TransferLeg, status: LegStatus) -> Self {
+        Self { leg, status }
+    }
+
+    /// Get a reference to the leg outcome's leg.
+    pub fn leg(&self) -> &TransferLeg {
+        &self.leg
+    }
+
+    /// Get a reference to the leg outcome's status.
+    pub fn status(&self) -> &LegStatus {
+        &self.status
+    }
+
+    /// Consume the outcome, returning its leg and status.
+    pub fn into_parts(self) -> (TransferLeg, LegStatus) {
+        (self.leg, self.status)
+    }
+}
+
+/// Outcome of each leg of a batch, in the order of the command.
+#[derive(Debug)]
+pub struct BatchTransferOutcome {
+    legs: Vec<LegOutcome>,
+}
+
+impl BatchTransferOutcome {
+    pub fn new(legs: Vec<LegOutcome>) -> Self {
+        Self { legs }
+    }
+
+    /// Whether every leg was completed, and the batch committed.
+    pub fn is_committed(&self) -> bool {
+        self.legs
+            .iter()
+            .all(|outcome| matches!(outcome.status, LegStatus::Completed))
+    }
+
+    /// Get a reference to the outcomes of the batch legs.
+    pub fn legs(&self) -> &[LegOutcome] {
+        self.legs.as_slice()
+    }
+
+    pub fn into_legs(self) -> Vec<LegOutcome> {
+        self.legs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::money::{tests::eur, Currency};
+
+    use super::*;
+
+    #[test]
+    fn it_builds_valid_commands() {
+        // When
+        let cmd = SendMoneyBatchCommand::try_new(
+            AccountId(1),
+            vec![(AccountId(2), eur(100)), (AccountId(3), eur(200))],
+            SendMoneyCommand::DEFAULT_CEILING,
+        )
+        .unwrap();
+
+        // Expect
+        assert_eq!(cmd.legs().len(), 2);
+        assert_eq!(*cmd.legs()[1].target_account_id(), AccountId(3));
+        assert_eq!(*cmd.legs()[1].money(), eur(200));
+    }
+
+    #[test]
+    fn it_rejects_empty_batches() {
+        // When
+        let err =
+            SendMoneyBatchCommand::try_new(AccountId(1), vec![], SendMoneyCommand::DEFAULT_CEILING)
+                .err()
+                .unwrap();
+
+        // Expect
+        assert_eq!(err.violations()[0].field(), "legs");
+    }
+
+    #[test]
+    fn it_rejects_invalid_legs_and_mixed_currencies() {
+        // When
+        let err = SendMoneyBatchCommand::try_new(
+            AccountId(1),
+            vec![
+                (AccountId(1), eur(100)),
+                (AccountId(3), Money::new(100, Currency::USD)),
+            ],
+            SendMoneyCommand::DEFAULT_CEILING,
+        )
+        .err()
+        .unwrap();
+
+        // Expect
+        let fields: Vec<_> = err.violations().iter().map(|v| v.field()).collect();
+        assert_eq!(fields, vec!["legs", "target_account_id"]);
+    }
+}
@@ -1,32 +1,136 @@
-use anyhow::Result;
+use derive_more::{Display, Error, From};
 use shaku::Interface;
 
-use crate::domain::{account::AccountId, money::Money};
+use super::{ValidationError, Validator};
+use crate::{
+    application::port::output::{ExchangeRateError, IdempotencyKey, PersistenceError},
+    domain::{
+        account::{AccountError, AccountId},
+        money::{Money, MoneyError},
+        scheduled_transfer::ScheduledTransferId,
+    },
+};
 
 #[rocket::async_trait]
 pub trait SendMoneyUseCase: Interface {
-    async fn send_money(&self, cmd: SendMoneyCommand) -> Result<bool>;
+    async fn send_money(&self, cmd: SendMoneyCommand) -> Result<(), SendMoneyError>;
+}
+
+#[derive(Debug, Display, Error, From)]
+pub enum SendMoneyError {
+    #[display(fmt = "Account {} not found", "_0.0")]
+    #[from(ignore)]
+    AccountNotFound(#[error(not(source))] AccountId),
+    #[display(
+        fmt = "Transfer of {} exceeds the maximum threshold of {}",
+        actual,
+        threshold
+    )]
+    ThresholdExceeded { threshold: Money, actual: Money },
+    #[display(fmt = "Idempotency key '{}' was already used for another transfer", _0)]
+    #[from(ignore)]
+    IdempotencyKeyReused(#[error(not(source))] IdempotencyKey),
+    #[display(fmt = "{}", _0)]
+    Account(AccountError),
+    #[display(fmt = "{}", _0)]
+    Money(MoneyError),
+    #[display(fmt = "{}", _0)]
+    ExchangeRate(ExchangeRateError),
+    #[display(fmt = "{}", _0)]
+    #[from(ignore)]
+    Persistence(PersistenceError),
+}
+
+impl From<PersistenceError> for SendMoneyError {
+    fn from(err: PersistenceError) -> Self {
+        match err {
+            PersistenceError::AccountNotFound(id) => SendMoneyError::AccountNotFound(id),
+            PersistenceError::IdempotencyKeyTaken(key) => SendMoneyError::IdempotencyKeyReused(key),
+            err => SendMoneyError::Persistence(err),
+        }
+    }
 }
 
 pub struct SendMoneyCommand {
     source_account_id: AccountId,
     target_account_id: AccountId,
     money: Money,
+    idempotency_key: Option<IdempotencyKey>,
 }
 
 impl SendMoneyCommand {
-    pub async fn try_new(
+    /// Default ceiling of the amount of a single command, in minor units of its currency.
+    pub const DEFAULT_CEILING: i64 = 1_000_000;
+
+    /// Maximum length of idempotency keys.
+    pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+    /// Build a command sending a positive amount of `money`, not exceeding `ceiling` minor units
+    /// of its currency, to another account.
+    pub fn try_new(
         source_account_id: AccountId,
         target_account_id: AccountId,
         money: Money,
-    ) -> Result<Self> {
+        ceiling: i64,
+    ) -> Result<Self, ValidationError> {
+        let mut validator = Validator::default();
+        validator.check(money.is_positive(), "money", "must be positive");
+        validator.check(
+            money.amount() <= ceiling,
+            "money",
+            format!("must not exceed {}", Money::new(ceiling, money.currency())),
+        );
+        validator.check(
+            source_account_id != target_account_id,
+            "target_account_id",
+            "must differ from the source account",
+        );
+        validator.finish()?;
+
         Ok(Self {
             source_account_id,
             target_account_id,
             money,
+            idempotency_key: None,
+        })
+    }
+
+    /// Identify the command by a client-chosen `key`, so that sending it again has no further
+    /// effect.
+    pub fn with_idempotency_key(self, key: impl Into<String>) -> Result<Self, ValidationError> {
+        let key = IdempotencyKey(key.into());
+        let mut validator = Validator::default();
+        validator.check(!key.0.is_empty(), "idempotency_key", "must not be empty");
+        validator.check(
+            !key.is_reserved(),
+            "idempotency_key",
+            format!("must not start with '{}'", IdempotencyKey::RESERVED_PREFIX),
+        );
+        validator.check(
+            key.0.chars().count() <= Self::MAX_IDEMPOTENCY_KEY_LEN,
+            "idempotency_key",
+            format!(
+                "must not exceed {} characters",
+                Self::MAX_IDEMPOTENCY_KEY_LEN
+            ),
+        );
+        validator.finish()?;
+
+        Ok(Self {
+            idempotency_key: Some(key),
+            ..self
         })
     }
 
+    /// Identify the command as the execution of scheduled transfer `id`, so that executing it
+    /// again has no further effect.
+    pub fn for_scheduled_transfer(self, id: ScheduledTransferId) -> Self {
+        Self {
+            idempotency_key: Some(IdempotencyKey::scheduled_transfer(id)),
+            ..self
+        }
+    }
+
     /// Get a reference to the send money command's source account id.
     pub fn source_account_id(&self) -> &AccountId {
         &self.source_account_id
@@ -41,4 +145,101 @@ impl SendMoneyCommand {
     pub fn money(&self) -> &Money {
         &self.money
     }
+
+    /// Get a reference to the send money command's idempotency key, if any.
+    pub fn idempotency_key(&self) -> Option<&IdempotencyKey> {
+        self.idempotency_key.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{application::port::input::FieldViolation, domain::money::tests::eur};
+
+    use super::*;
+
+    #[test]
+    fn it_builds_valid_commands() {
+        // When
+        let cmd = SendMoneyCommand::try_new(
+            AccountId(1),
+            AccountId(2),
+            eur(500),
+            SendMoneyCommand::DEFAULT_CEILING,
+        );
+
+        // Expect
+        assert!(cmd.is_ok());
+    }
+
+    #[test]
+    fn it_rejects_non_positive_amounts() {
+        for amount in &[0, -1] {
+            // When
+            let err = SendMoneyCommand::try_new(
+                AccountId(1),
+                AccountId(2),
+                eur(*amount),
+                SendMoneyCommand::DEFAULT_CEILING,
+            )
+            .err()
+            .unwrap();
+
+            // Expect
+            assert_eq!(err.violations().len(), 1);
+            assert_eq!(err.violations()[0].field(), "money");
+        }
+    }
+
+    #[test]
+    fn it_rejects_amounts_above_ceiling() {
+        // When
+        let within = SendMoneyCommand::try_new(AccountId(1), AccountId(2), eur(100), 100);
+        let above = SendMoneyCommand::try_new(AccountId(1), AccountId(2), eur(101), 100);
+
+        // Expect
+        assert!(within.is_ok());
+        assert_eq!(above.err().unwrap().violations()[0].field(), "money");
+    }
+
+    #[test]
+    fn it_lists_every_violated_field() {
+        // When
+        let err = SendMoneyCommand::try_new(
+            AccountId(1),
+            AccountId(1),
+            eur(0),
+            SendMoneyCommand::DEFAULT_CEILING,
+        )
+        .err()
+        .unwrap();
+
+        // Expect
+        let fields: Vec<_> = err.violations().iter().map(FieldViolation::field).collect();
+        assert_eq!(fields, vec!["money", "target_account_id"]);
+    }
+
+    #[test]
+    fn it_rejects_invalid_idempotency_keys() {
+        for key in &[
+            "".to_owned(),
+            "k".repeat(256),
+            "@scheduled-transfer-1".to_owned(),
+        ] {
+            // When
+            let err = SendMoneyCommand::try_new(
+                AccountId(1),
+                AccountId(2),
+                eur(500),
+                SendMoneyCommand::DEFAULT_CEILING,
+            )
+            .unwrap()
+            .with_idempotency_key(key.as_str())
+            .err()
+            .unwrap();
+
+            // Expect
+            assert_eq!(err.violations()[0].field(), "idempotency_key");
+        }
+    }
 }
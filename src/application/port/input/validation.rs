@@ -0,0 +1,94 @@
+use std::fmt;
+
+use derive_more::Error;
+
+/// Error returned when an input command cannot be built, listing every violated field.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub struct ValidationError {
+    #[error(not(source))]
+    violations: Vec<FieldViolation>,
+}
+
+impl ValidationError {
+    /// Get a reference to the validation error's violations.
+    pub fn violations(&self) -> &[FieldViolation] {
+        self.violations.as_slice()
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid command: ")?;
+        for (i, violation) in self.violations.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", violation)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldViolation {
+    field: &'static str,
+    message: String,
+}
+
+impl FieldViolation {
+    /// Get a reference to the name of the violated field.
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
+
+    /// Get a reference to the field violation's message.
+    pub fn message(&self) -> &str {
+        self.message.as_str()
+    }
+}
+
+impl fmt::Display for FieldViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Collects field violations while validating a command.
+#[derive(Debug, Default)]
+pub struct Validator {
+    violations: Vec<FieldViolation>,
+}
+
+impl Validator {
+    /// Record a violation of `field` unless `condition` holds.
+    pub fn check(&mut self, condition: bool, field: &'static str, message: impl Into<String>) {
+        if !condition {
+            self.violations.push(FieldViolation {
+                field,
+                message: message.into(),
+            });
+        }
+    }
+
+    /// Record the violations of a nested command, returning it when valid.
+    pub fn include<T>(&mut self, result: Result<T, ValidationError>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(err) => {
+                self.violations.extend(err.violations);
+                None
+            }
+        }
+    }
+
+    pub fn finish(self) -> Result<(), ValidationError> {
+        if self.violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError {
+                violations: self.violations,
+            })
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use crate::domain::account::AccountId;
+
+use super::PersistenceError;
+
+/// Grants exclusive access to accounts, serializing concurrent updates of their state.
+///
+/// Locks are held by the unit of work acquiring them, and released once it is committed or
+/// rolled back at the latest. Callers locking more than one account must acquire locks in
+/// ascending `AccountId` order.
+#[rocket::async_trait]
+pub trait AccountLock: Send {
+    /// Wait until the account is not locked by anyone else, then lock it.
+    async fn lock_account(&mut self, account_id: AccountId) -> Result<(), PersistenceError>;
+
+    /// Release the account, unless its lock is bound to the end of the unit of work.
+    async fn release_account(&mut self, account_id: AccountId) -> Result<(), PersistenceError>;
+}
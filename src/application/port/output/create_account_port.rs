@@ -0,0 +1,13 @@
+use shaku::Interface;
+
+use crate::domain::account::Account;
+
+use super::PersistenceError;
+
+#[rocket::async_trait]
+pub trait CreateAccountPort: Interface {
+    /// Insert a new account, failing if its id is already taken.
+    ///
+    /// Ids generated afterwards are past the id of the account, even when it was not generated.
+    async fn create_account(&self, account: &Account) -> Result<(), PersistenceError>;
+}
@@ -0,0 +1,39 @@
+use derive_more::{Display, Error, From};
+
+use super::IdempotencyKey;
+use crate::domain::{
+    account::AccountId,
+    activity::{ActivityId, TransferId},
+    customer::CustomerId,
+};
+
+/// Error returned by output ports backed by a persistent storage.
+#[derive(Debug, Display, Error, From)]
+pub enum PersistenceError {
+    #[display(fmt = "Account {} not found", "_0.0")]
+    #[from(ignore)]
+    AccountNotFound(#[error(not(source))] AccountId),
+    #[display(fmt = "Account {} already exists", "_0.0")]
+    #[from(ignore)]
+    AccountAlreadyExists(#[error(not(source))] AccountId),
+    #[display(fmt = "Activity {} not found", "_0.0")]
+    #[from(ignore)]
+    ActivityNotFound(#[error(not(source))] ActivityId),
+    #[display(fmt = "Transfer {} not found", "_0.0")]
+    #[from(ignore)]
+    TransferNotFound(#[error(not(source))] TransferId),
+    #[display(fmt = "Customer {} not found", "_0.0")]
+    #[from(ignore)]
+    CustomerNotFound(#[error(not(source))] CustomerId),
+    #[display(fmt = "Idempotency key '{}' is already taken", _0)]
+    #[from(ignore)]
+    IdempotencyKeyTaken(#[error(not(source))] IdempotencyKey),
+    #[display(fmt = "Account Id is not set")]
+    MissingAccountId,
+    #[display(fmt = "Corrupted data: {}", _0)]
+    #[from(ignore)]
+    CorruptedData(#[error(not(source))] String),
+    #[display(fmt = "Database error: {}", _0)]
+    #[from(ignore)]
+    Database(#[error(not(source))] Box<dyn std::error::Error + Send + Sync>),
+}
@@ -0,0 +1,25 @@
+use derive_more::{Display, Error};
+use shaku::Interface;
+
+use crate::domain::{exchange_rate::ExchangeRate, money::Currency};
+
+/// Provides the rates at which amounts are converted between currencies.
+#[rocket::async_trait]
+pub trait ExchangeRatePort: Interface {
+    /// Get the current rate converting `source` amounts into `target` amounts.
+    async fn exchange_rate(
+        &self,
+        source: Currency,
+        target: Currency,
+    ) -> Result<ExchangeRate, ExchangeRateError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Display, Error)]
+pub enum ExchangeRateError {
+    #[display(fmt = "No exchange rate from {} to {}", source, target)]
+    Unavailable {
+        #[error(not(source))]
+        source: Currency,
+        target: Currency,
+    },
+}
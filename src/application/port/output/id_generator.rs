@@ -0,0 +1,13 @@
+use shaku::Interface;
+
+use crate::domain::{account::AccountId, activity::TransferId};
+
+use super::PersistenceError;
+
+/// Hands out unique ids for new entities.
+#[rocket::async_trait]
+pub trait IdGenerator: Interface {
+    async fn next_account_id(&self) -> Result<AccountId, PersistenceError>;
+
+    async fn next_transfer_id(&self) -> Result<TransferId, PersistenceError>;
+}
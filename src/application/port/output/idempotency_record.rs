@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use derive_more::Display;
+
+use crate::domain::{account::AccountId, money::Money, scheduled_transfer::ScheduledTransferId};
+
+/// Key chosen by a client to identify a request, so that retrying it has no further effect.
+///
+/// Keys starting with `RESERVED_PREFIX` identify transfers sent by the application itself, and
+/// cannot be chosen by clients.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Display)]
+pub struct IdempotencyKey(pub String);
+
+impl IdempotencyKey {
+    pub const RESERVED_PREFIX: &'static str = "@";
+
+    /// Key identifying the transfer sent by executing scheduled transfer `id`.
+    pub fn scheduled_transfer(id: ScheduledTransferId) -> Self {
+        Self(format!(
+            "{}scheduled-transfer-{}",
+            Self::RESERVED_PREFIX,
+            id.0
+        ))
+    }
+
+    /// Whether the key is reserved to transfers sent by the application itself.
+    pub fn is_reserved(&self) -> bool {
+        self.0.starts_with(Self::RESERVED_PREFIX)
+    }
+}
+
+/// Transfer completed on behalf of a request carrying an idempotency key.
+///
+/// Keys are scoped to the source account, so clients sending from different accounts may choose
+/// the same key. Records are saved by the unit of work moving the money: a failed request leaves
+/// none and may be retried.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdempotencyRecord {
+    key: IdempotencyKey,
+    source_account_id: AccountId,
+    target_account_id: AccountId,
+    money: Money,
+    created_at: DateTime<Utc>,
+}
+
+impl IdempotencyRecord {
+    pub fn new(
+        key: IdempotencyKey,
+        source_account_id: AccountId,
+        target_account_id: AccountId,
+        money: Money,
+        created_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            key,
+            source_account_id,
+            target_account_id,
+            money,
+            created_at,
+        }
+    }
+
+    /// Whether the record was left by a transfer of `money` to the same target account.
+    pub fn matches(&self, target: &AccountId, money: &Money) -> bool {
+        &self.target_account_id == target && &self.money == money
+    }
+
+    /// Get a reference to the idempotency record's key.
+    pub fn key(&self) -> &IdempotencyKey {
+        &self.key
+    }
+
+    /// Get a reference to the idempotency record's source account id.
+    pub fn source_account_id(&self) -> &AccountId {
+        &self.source_account_id
+    }
+
+    /// Get a reference to the idempotency record's target account id.
+    pub fn target_account_id(&self) -> &AccountId {
+        &self.target_account_id
+    }
+
+    /// Get a reference to the idempotency record's money.
+    pub fn money(&self) -> &Money {
+        &self.money
+    }
+
+    /// Get a reference to the idempotency record's creation time.
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+}
@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::account::AccountId;
+
+use super::{IdempotencyKey, IdempotencyRecord, PersistenceError};
+
+/// Remembers transfers completed on behalf of requests carrying an idempotency key.
+///
+/// Records are found and saved by the unit of work moving the money, so that they are persisted
+/// along with its updates, and discarded when it is rolled back.
+#[rocket::async_trait]
+pub trait IdempotencyStore: Send {
+    /// Find the record left for `key` by a transfer from `source_account_id` at or after `since`.
+    async fn find_idempotency_record(
+        &mut self,
+        source_account_id: AccountId,
+        key: &IdempotencyKey,
+        since: DateTime<Utc>,
+    ) -> Result<Option<IdempotencyRecord>, PersistenceError>;
+
+    /// Store `record`, replacing any record left for the same source account and key before
+    /// `since`.
+    ///
+    /// Fails with `IdempotencyKeyTaken` if a record was left since then, e.g. by a concurrent
+    /// transfer.
+    async fn save_idempotency_record(
+        &mut self,
+        record: &IdempotencyRecord,
+        since: DateTime<Utc>,
+    ) -> Result<(), PersistenceError>;
+}
@@ -0,0 +1,13 @@
+use shaku::Interface;
+
+use crate::domain::activity::{Activity, ActivityId};
+
+use super::PersistenceError;
+
+#[rocket::async_trait]
+pub trait LoadActivityPort: Interface {
+    async fn load_activity(&self, activity_id: ActivityId) -> Result<Activity, PersistenceError>;
+
+    /// Load every activity of every account, ordered by id.
+    async fn load_activities(&self) -> Result<Vec<Activity>, PersistenceError>;
+}
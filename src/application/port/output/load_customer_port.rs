@@ -0,0 +1,10 @@
+use shaku::Interface;
+
+use crate::domain::customer::{Customer, CustomerId};
+
+use super::PersistenceError;
+
+#[rocket::async_trait]
+pub trait LoadCustomerPort: Interface {
+    async fn load_customer(&self, customer_id: CustomerId) -> Result<Customer, PersistenceError>;
+}
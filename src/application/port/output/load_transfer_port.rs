@@ -0,0 +1,15 @@
+use shaku::Interface;
+
+use crate::domain::activity::{Activity, TransferId};
+
+use super::PersistenceError;
+
+#[rocket::async_trait]
+pub trait LoadTransferPort: Interface {
+    /// Load the activities recording transfer `transfer_id`, owned by its source and target
+    /// accounts, ordered by id.
+    async fn load_transfer(
+        &self,
+        transfer_id: TransferId,
+    ) -> Result<Vec<Activity>, PersistenceError>;
+}
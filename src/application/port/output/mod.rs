@@ -1,5 +1,29 @@
+mod account_lock;
+mod create_account_port;
+mod error;
+mod exchange_rate_port;
+mod id_generator;
+mod idempotency_record;
+mod idempotency_store;
 mod load_account_port;
+mod load_activity_port;
+mod load_customer_port;
+mod load_transfer_port;
+mod scheduled_transfer_port;
+mod unit_of_work;
 mod update_account_state_port;
 
+pub use account_lock::*;
+pub use create_account_port::*;
+pub use error::*;
+pub use exchange_rate_port::*;
+pub use id_generator::*;
+pub use idempotency_record::*;
+pub use idempotency_store::*;
 pub use load_account_port::*;
+pub use load_activity_port::*;
+pub use load_customer_port::*;
+pub use load_transfer_port::*;
+pub use scheduled_transfer_port::*;
+pub use unit_of_work::*;
 pub use update_account_state_port::*;
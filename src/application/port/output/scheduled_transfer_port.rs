@@ -0,0 +1,30 @@
+use chrono::{DateTime, Duration, Utc};
+use shaku::Interface;
+
+use crate::domain::scheduled_transfer::{ScheduledTransfer, ScheduledTransferId};
+
+use super::PersistenceError;
+
+#[rocket::async_trait]
+pub trait ScheduledTransferPort: Interface {
+    /// Store a new pending transfer.
+    async fn schedule(
+        &self,
+        transfer: &ScheduledTransfer,
+    ) -> Result<ScheduledTransferId, PersistenceError>;
+
+    /// Claim up to `limit` transfers due at `now`, oldest first, marking them as executing so
+    /// that no other worker executes them too.
+    ///
+    /// Transfers still executing more than `claim_timeout` after they were claimed are claimed
+    /// again.
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        claim_timeout: Duration,
+        limit: u32,
+    ) -> Result<Vec<ScheduledTransfer>, PersistenceError>;
+
+    /// Persist the status, execution time and failure of a claimed transfer.
+    async fn record_outcome(&self, transfer: &ScheduledTransfer) -> Result<(), PersistenceError>;
+}
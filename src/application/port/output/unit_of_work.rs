@@ -0,0 +1,62 @@
+use chrono::{DateTime, Utc};
+use shaku::Interface;
+
+use crate::domain::{
+    account::{Account, AccountId},
+    activity::ActivityId,
+};
+
+use super::{AccountLock, IdempotencyStore, PersistenceError};
+
+/// Starts units of work, grouping loads and updates of several accounts into a single atomic
+/// transaction.
+#[rocket::async_trait]
+pub trait UnitOfWorkPort: Interface {
+    async fn begin(&self) -> Result<Box<dyn UnitOfWork>, PersistenceError>;
+}
+
+/// Loads and updates accounts within a single transaction.
+///
+/// Nothing is persisted until `commit` is called. Dropping a unit of work without committing it
+/// rolls back every update, and releases its locks.
+#[rocket::async_trait]
+pub trait UnitOfWork: AccountLock + IdempotencyStore {
+    /// Wait until none of `account_ids` is locked by another unit of work, then lock them until
+    /// this one is committed or rolled back, serializing concurrent updates of their state.
+    ///
+    /// Accounts are locked in ascending order, so units of work locking the same accounts cannot
+    /// deadlock each other.
+    async fn lock_accounts(&mut self, account_ids: &[AccountId]) -> Result<(), PersistenceError> {
+        let mut account_ids = account_ids.to_vec();
+        account_ids.sort();
+        account_ids.dedup();
+
+        for account_id in account_ids {
+            self.lock_account(account_id).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn load_account(
+        &mut self,
+        account_id: AccountId,
+        baseline_date: DateTime<Utc>,
+    ) -> Result<Account, PersistenceError>;
+
+    async fn update_activities(&mut self, account: &Account) -> Result<Account, PersistenceError>;
+
+    /// Find an activity compensating `activity_id`, or any other activity recording the same
+    /// transfer, if the transfer was reversed.
+    async fn find_reversal(
+        &mut self,
+        activity_id: ActivityId,
+    ) -> Result<Option<ActivityId>, PersistenceError>;
+
+    /// Persist the status of an existing account.
+    async fn update_status(&mut self, account: &Account) -> Result<(), PersistenceError>;
+
+    async fn commit(self: Box<Self>) -> Result<(), PersistenceError>;
+
+    async fn rollback(self: Box<Self>) -> Result<(), PersistenceError>;
+}
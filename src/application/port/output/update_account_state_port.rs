@@ -1,10 +1,10 @@
-use anyhow::Result;
-
 use shaku::Interface;
 
 use crate::domain::account::Account;
 
+use super::PersistenceError;
+
 #[rocket::async_trait]
 pub trait UpdateAccountStatePort: Interface {
-    async fn update_activities(&self, account: &Account) -> Result<Account>;
+    async fn update_activities(&self, account: &Account) -> Result<Account, PersistenceError>;
 }
@@ -0,0 +1,344 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+
+use crate::domain::activity::{Activity, ActivityId, TransferId};
+
+use super::{
+    port::{
+        input::{ReverseTransferError, ReverseTransferUseCase},
+        output::{IdGenerator, LoadActivityPort, PersistenceError, UnitOfWork, UnitOfWorkPort},
+    },
+    BASELINE_WINDOW_DAYS,
+};
+
+#[derive(Component)]
+#[shaku(interface = ReverseTransferUseCase)]
+pub struct ReverseTransferService {
+    #[shaku(inject)]
+    load_activity_port: Arc<dyn LoadActivityPort>,
+    #[shaku(inject)]
+    unit_of_work_port: Arc<dyn UnitOfWorkPort>,
+    #[shaku(inject)]
+    id_generator: Arc<dyn IdGenerator>,
+}
+
+#[rocket::async_trait]
+impl ReverseTransferUseCase for ReverseTransferService {
+    async fn reverse_transfer(
+        &self,
+        activity_id: ActivityId,
+    ) -> Result<Vec<Activity>, ReverseTransferError> {
+        let original = self.load_activity_port.load_activity(activity_id).await?;
+        if original.reverses_activity_id().is_some() {
+            return Err(ReverseTransferError::ReversalNotReversible(activity_id));
+        }
+        // Without a transfer id, a reversal through the other activity could not be detected
+        if original.transfer_id().is_none() {
+            return Err(ReverseTransferError::UnlinkedActivity(activity_id));
+        }
+
+        self.reverse(&original).await
+    }
+}
+
+impl ReverseTransferService {
+    /// Compensate `original` within a single unit of work, rolling it back on failure.
+    async fn reverse(&self, original: &Activity) -> Result<Vec<Activity>, ReverseTransferError> {
+        let transfer_id = self.id_generator.next_transfer_id().await?;
+        let mut uow = self.unit_of_work_port.begin().await?;
+
+        match Self::reverse_within(uow.as_mut(), original, transfer_id).await {
+            Ok(reversals) => {
+                uow.commit().await?;
+                Ok(reversals)
+            }
+            Err(err) => {
+                if let Err(rollback_err) = uow.rollback().await {
+                    log::error!("Unable to roll back transfer reversal: {}", rollback_err);
+                }
+                Err(err)
+            }
+        }
+    }
+
+    async fn reverse_within(
+        uow: &mut dyn UnitOfWork,
+        original: &Activity,
+        transfer_id: TransferId,
+    ) -> Result<Vec<Activity>, ReverseTransferError> {
+        let original_id = *original.id().ok_or_else(|| {
+            PersistenceError::CorruptedData("Loaded activity has no id".to_owned())
+        })?;
+        // Checked while both accounts are locked, so that concurrent reversals cannot both pass
+        uow.lock_accounts(&[*original.source_account_id(), *original.target_account_id()])
+            .await?;
+        if let Some(reversal) = uow.find_reversal(original_id).await? {
+            return Err(ReverseTransferError::AlreadyReversed {
+                original: original_id,
+                reversal,
+            });
+        }
+
+        let baseline_date = Utc::now() - Duration::days(BASELINE_WINDOW_DAYS);
+
+        let mut target_account = uow
+            .load_account(*original.target_account_id(), baseline_date)
+            .await?;
+        let mut source_account = uow
+            .load_account(*original.source_account_id(), baseline_date)
+            .await?;
+        target_account.reverse(original, transfer_id)?;
+        source_account.reverse(original, transfer_id)?;
+
+        let target_account = uow.update_activities(&target_account).await?;
+        let source_account = uow.update_activities(&source_account).await?;
+
+        Ok([target_account, source_account]
+            .iter()
+            .flat_map(|account| account.activity_window().activities())
+            .filter(|activity| activity.reverses_activity_id() == Some(&original_id))
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use rocket::tokio;
+
+    use crate::{
+        application::tests::{MockIdGenerator, MockLoadActivityPort, MockUnitOfWorkPort},
+        domain::{
+            account::{AccountError, AccountId},
+            activity::tests::default_activity,
+            money::tests::eur,
+        },
+    };
+
+    use super::*;
+
+    fn service_with(
+        uow_port: MockUnitOfWorkPort,
+        activities: Vec<Activity>,
+    ) -> (ReverseTransferService, Arc<MockUnitOfWorkPort>) {
+        let uow_port = Arc::new(uow_port);
+        let service = ReverseTransferService {
+            load_activity_port: Arc::new(MockLoadActivityPort::with_activities(activities)),
+            unit_of_work_port: uow_port.clone(),
+            id_generator: Arc::new(MockIdGenerator::default()),
+        };
+
+        (service, uow_port)
+    }
+
+    /// Transfer `3` of 300 from account `41` to account `42`, recorded as activity `7`.
+    fn transfer() -> Activity {
+        default_activity()
+            .id(Some(ActivityId(7)))
+            .owner_account_id(AccountId(41))
+            .source_account_id(AccountId(41))
+            .target_account_id(AccountId(42))
+            .money(eur(300))
+            .transfer_id(Some(TransferId(3)))
+            .build()
+            .unwrap()
+    }
+
+    /// Both activities recording transfer `3`: `7` owned by account `41`, and `8` owned by
+    /// account `42`.
+    fn mirrored_transfer() -> Vec<Activity> {
+        [(7, 41), (8, 42)]
+            .iter()
+            .map(|(id, owner)| {
+                default_activity()
+                    .id(Some(ActivityId(*id)))
+                    .owner_account_id(AccountId(*owner))
+                    .source_account_id(AccountId(41))
+                    .target_account_id(AccountId(42))
+                    .money(eur(300))
+                    .transfer_id(Some(TransferId(3)))
+                    .build()
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn it_reverses_transfers() -> Result<()> {
+        // Given
+        let (service, uow_port) = service_with(
+            MockUnitOfWorkPort::with_balances(&[
+                (AccountId(41), eur(200)),
+                (AccountId(42), eur(300)),
+            ]),
+            vec![transfer()],
+        );
+
+        // When
+        let reversals = service.reverse_transfer(ActivityId(7)).await?;
+
+        // Expect
+        assert_eq!(reversals.len(), 2);
+        let state = uow_port.state();
+        assert_eq!(state.committed.len(), 2);
+
+        let (withdrawing, withdrawals) = &state.committed[0];
+        assert_eq!(*withdrawing, AccountId(42));
+        assert_eq!(*withdrawals[0].source_account_id(), AccountId(42));
+        assert_eq!(*withdrawals[0].target_account_id(), AccountId(41));
+        assert_eq!(*withdrawals[0].money(), eur(300));
+        assert_eq!(withdrawals[0].reverses_activity_id(), Some(&ActivityId(7)));
+
+        let (depositing, deposits) = &state.committed[1];
+        assert_eq!(*depositing, AccountId(41));
+        assert_eq!(*deposits[0].owner_account_id(), AccountId(41));
+        assert_eq!(deposits[0].reverses_activity_id(), Some(&ActivityId(7)));
+        assert!(deposits[0].transfer_id().is_some());
+        assert_eq!(deposits[0].transfer_id(), withdrawals[0].transfer_id());
+
+        assert_eq!(state.locked, vec![AccountId(41), AccountId(42)]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_already_reversed_transfer_then_reversal_fails() -> Result<()> {
+        // Given
+        let (service, uow_port) = service_with(
+            MockUnitOfWorkPort::with_balances(&[
+                (AccountId(41), eur(200)),
+                (AccountId(42), eur(300)),
+            ])
+            .with_reversal(ActivityId(7), ActivityId(9)),
+            vec![transfer()],
+        );
+
+        // When
+        let result = service.reverse_transfer(ActivityId(7)).await;
+
+        // Expect
+        assert!(matches!(
+            result,
+            Err(ReverseTransferError::AlreadyReversed {
+                original: ActivityId(7),
+                reversal: ActivityId(9),
+            })
+        ));
+        assert!(uow_port.state().committed.is_empty());
+        assert_eq!(uow_port.state().rollbacks, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_transfer_reversed_through_its_mirror_then_reversal_fails() -> Result<()> {
+        // Given
+        let (service, uow_port) = service_with(
+            MockUnitOfWorkPort::with_balances(&[
+                (AccountId(41), eur(200)),
+                (AccountId(42), eur(300)),
+            ])
+            .with_transfer(&[ActivityId(7), ActivityId(8)])
+            .with_reversal(ActivityId(8), ActivityId(9)),
+            mirrored_transfer(),
+        );
+
+        // When
+        let result = service.reverse_transfer(ActivityId(7)).await;
+
+        // Expect
+        assert!(matches!(
+            result,
+            Err(ReverseTransferError::AlreadyReversed {
+                original: ActivityId(7),
+                reversal: ActivityId(9),
+            })
+        ));
+        assert!(uow_port.state().committed.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_spent_money_then_reversal_fails() -> Result<()> {
+        // Given
+        let (service, uow_port) = service_with(
+            MockUnitOfWorkPort::with_balances(&[
+                (AccountId(41), eur(200)),
+                (AccountId(42), eur(100)),
+            ]),
+            vec![transfer()],
+        );
+
+        // When
+        let result = service.reverse_transfer(ActivityId(7)).await;
+
+        // Expect
+        assert!(matches!(
+            result,
+            Err(ReverseTransferError::Account(
+                AccountError::InsufficientFunds { .. }
+            ))
+        ));
+        assert!(uow_port.state().committed.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_activity_without_transfer_id_then_reversal_fails() -> Result<()> {
+        // Given
+        let unlinked = default_activity()
+            .id(Some(ActivityId(7)))
+            .owner_account_id(AccountId(41))
+            .source_account_id(AccountId(41))
+            .target_account_id(AccountId(42))
+            .money(eur(300))
+            .build()
+            .unwrap();
+        let (service, uow_port) = service_with(
+            MockUnitOfWorkPort::with_balances(&[
+                (AccountId(41), eur(200)),
+                (AccountId(42), eur(300)),
+            ]),
+            vec![unlinked],
+        );
+
+        // When
+        let result = service.reverse_transfer(ActivityId(7)).await;
+
+        // Expect
+        assert!(matches!(
+            result,
+            Err(ReverseTransferError::UnlinkedActivity(ActivityId(7)))
+        ));
+        assert!(uow_port.state().locked.is_empty());
+        assert!(uow_port.state().committed.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reversals_cannot_be_reversed() -> Result<()> {
+        // Given
+        let reversal = default_activity()
+            .id(Some(ActivityId(9)))
+            .reverses_activity_id(Some(ActivityId(7)))
+            .build()
+            .unwrap();
+        let (service, uow_port) = service_with(MockUnitOfWorkPort::default(), vec![reversal]);
+
+        // When
+        let result = service.reverse_transfer(ActivityId(9)).await;
+        let unknown = service.reverse_transfer(ActivityId(99)).await;
+
+        // Expect
+        assert!(matches!(
+            result,
+            Err(ReverseTransferError::ReversalNotReversible(ActivityId(9)))
+        ));
+        assert!(matches!(
+            unknown,
+            Err(ReverseTransferError::ActivityNotFound(ActivityId(99)))
+        ));
+        assert!(uow_port.state().locked.is_empty());
+        Ok(())
+    }
+}
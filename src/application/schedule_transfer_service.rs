@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+
+use crate::domain::scheduled_transfer::ScheduledTransferId;
+
+use super::port::{
+    input::{ScheduleTransferCommand, ScheduleTransferError, ScheduleTransferUseCase},
+    output::{LoadAccountPort, ScheduledTransferPort},
+};
+
+#[derive(Component)]
+#[shaku(interface = ScheduleTransferUseCase)]
+pub struct ScheduleTransferService {
+    #[shaku(inject)]
+    load_account_port: Arc<dyn LoadAccountPort>,
+    #[shaku(inject)]
+    scheduled_transfer_port: Arc<dyn ScheduledTransferPort>,
+}
+
+#[rocket::async_trait]
+impl ScheduleTransferUseCase for ScheduleTransferService {
+    async fn schedule_transfer(
+        &self,
+        cmd: ScheduleTransferCommand,
+    ) -> Result<ScheduledTransferId, ScheduleTransferError> {
+        // Only the existence of accounts matters, not their activities
+        let transfer = cmd.transfer();
+        for account_id in [transfer.source_account_id(), transfer.target_account_id()] {
+            self.load_account_port
+                .load_account(*account_id, Utc::now())
+                .await?;
+        }
+
+        Ok(self
+            .scheduled_transfer_port
+            .schedule(&cmd.to_scheduled_transfer())
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use chrono::Duration;
+    use rocket::tokio;
+
+    use crate::{
+        application::{
+            port::input::SendMoneyCommand,
+            tests::{MockLoadAccountPort, MockScheduledTransferPort},
+        },
+        domain::{account::AccountId, money::tests::eur},
+    };
+
+    use super::*;
+
+    fn service_with(
+        account_ids: &[AccountId],
+    ) -> (ScheduleTransferService, Arc<MockScheduledTransferPort>) {
+        let port = Arc::new(MockScheduledTransferPort::default());
+        let service = ScheduleTransferService {
+            load_account_port: Arc::new(MockLoadAccountPort::with_accounts(account_ids)),
+            scheduled_transfer_port: port.clone(),
+        };
+
+        (service, port)
+    }
+
+    fn command(target_account_id: AccountId) -> Result<ScheduleTransferCommand> {
+        Ok(ScheduleTransferCommand::try_new(
+            AccountId(1),
+            target_account_id,
+            eur(100),
+            Utc::now() + Duration::hours(1),
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?)
+    }
+
+    #[tokio::test]
+    async fn it_schedules_transfers() -> Result<()> {
+        // Given
+        let (service, port) = service_with(&[AccountId(1), AccountId(2)]);
+
+        // When
+        let id = service.schedule_transfer(command(AccountId(2))?).await?;
+
+        // Expect
+        assert_eq!(id, ScheduledTransferId(0));
+        assert_eq!(port.transfers().len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_unknown_target_account_then_nothing_is_scheduled() -> Result<()> {
+        // Given
+        let (service, port) = service_with(&[AccountId(1)]);
+
+        // When
+        let result = service.schedule_transfer(command(AccountId(3))?).await;
+
+        // Expect
+        assert!(matches!(
+            result,
+            Err(ScheduleTransferError::AccountNotFound(AccountId(3)))
+        ));
+        assert!(port.transfers().is_empty());
+        Ok(())
+    }
+}
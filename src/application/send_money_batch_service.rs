@@ -0,0 +1,464 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    sync::Arc,
+};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::domain::{
+    account::{Account, AccountError, AccountId},
+    fee::FeeContext,
+    money::Money,
+};
+
+use super::{
+    money_transfer_properties::MoneyTransferProperties,
+    port::{
+        input::{
+            BatchTransferOutcome, LegOutcome, LegStatus, SendMoneyBatchCommand,
+            SendMoneyBatchUseCase, SendMoneyError, TransferLeg,
+        },
+        output::{ExchangeRatePort, IdGenerator, UnitOfWork, UnitOfWorkPort},
+    },
+    send_money_service::{ensure_within_threshold, move_money},
+    BASELINE_WINDOW_DAYS,
+};
+
+#[derive(Component)]
+#[shaku(interface = SendMoneyBatchUseCase)]
+pub struct SendMoneyBatchService {
+    #[shaku(inject)]
+    unit_of_work_port: Arc<dyn UnitOfWorkPort>,
+    #[shaku(inject)]
+    exchange_rate_port: Arc<dyn ExchangeRatePort>,
+    #[shaku(inject)]
+    money_transfer_properties: Arc<dyn MoneyTransferProperties>,
+    #[shaku(inject)]
+    id_generator: Arc<dyn IdGenerator>,
+}
+
+#[rocket::async_trait]
+impl SendMoneyBatchUseCase for SendMoneyBatchService {
+    /// Send every leg within a single unit of work, committed only when all of them succeed.
+    async fn send_money_batch(
+        &self,
+        cmd: SendMoneyBatchCommand,
+    ) -> Result<BatchTransferOutcome, SendMoneyError> {
+        let mut uow = self.unit_of_work_port.begin().await?;
+
+        match self.transfer_batch_within(uow.as_mut(), &cmd).await {
+            Ok(outcome) if outcome.is_committed() => {
+                uow.commit().await?;
+                Ok(outcome)
+            }
+            result => {
+                if let Err(rollback_err) = uow.rollback().await {
+                    log::error!("Unable to roll back batch transfer: {}", rollback_err);
+                }
+                result
+            }
+        }
+    }
+}
+
+impl SendMoneyBatchService {
+    async fn transfer_batch_within(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        cmd: &SendMoneyBatchCommand,
+    ) -> Result<BatchTransferOutcome, SendMoneyError> {
+        let baseline_date = Utc::now() - Duration::days(BASELINE_WINDOW_DAYS);
+        let source_id = *cmd.source_account_id();
+
+        let mut account_ids = vec![source_id];
+        account_ids.extend(cmd.legs().iter().map(|leg| *leg.target_account_id()));
+        account_ids.extend(self.money_transfer_properties.fee_account_id());
+        uow.lock_accounts(&account_ids).await?;
+
+        let mut source_account = uow.load_account(source_id, baseline_date).await?;
+        let mut accounts = HashMap::new();
+        let fee_account_id = self
+            .money_transfer_properties
+            .fee_account_id()
+            .filter(|id| *id != source_id);
+
+        let mut fees = Vec::with_capacity(cmd.legs().len());
+        for leg in cmd.legs() {
+            let fee = self
+                .prepare_leg(
+                    uow,
+                    &source_account,
+                    &mut accounts,
+                    leg,
+                    fee_account_id,
+                    baseline_date,
+                )
+                .await;
+            fees.push(Self::leg_result(fee)?);
+        }
+        if fees.iter().any(Result::is_err) {
+            return Ok(Self::outcome(cmd, fees));
+        }
+        let fees: Vec<Money> = fees.into_iter().flatten().collect();
+
+        // Funds are checked once for the whole batch, fees included
+        let total = cmd
+            .legs()
+            .iter()
+            .map(TransferLeg::money)
+            .chain(&fees)
+            .sum::<Result<Money, _>>()?;
+        source_account.ensure_may_withdraw(total)?;
+
+        if let Some(fee_account_id) = fee_account_id.filter(|_| fees.iter().any(Money::is_positive))
+        {
+            if let Entry::Vacant(entry) = accounts.entry(fee_account_id) {
+                entry.insert(uow.load_account(fee_account_id, baseline_date).await?);
+            }
+        }
+
+        let mut results = Vec::with_capacity(cmd.legs().len());
+        for (leg, fee) in cmd.legs().iter().zip(fees) {
+            let result = self
+                .send_leg(&mut source_account, &mut accounts, leg, fee, fee_account_id)
+                .await;
+            results.push(Self::leg_result(result)?);
+        }
+
+        let outcome = Self::outcome(cmd, results);
+        if outcome.is_committed() {
+            uow.update_activities(&source_account).await?;
+
+            let mut account_ids: Vec<_> = accounts.keys().copied().collect();
+            account_ids.sort();
+            for account_id in account_ids {
+                uow.update_activities(&accounts[&account_id]).await?;
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    /// Check `leg` against the transfer threshold and load its target account, returning the
+    /// fee charged for it: fees are only charged when collected by a fee account.
+    async fn prepare_leg(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        source_account: &Account,
+        accounts: &mut HashMap<AccountId, Account>,
+        leg: &TransferLeg,
+        fee_account_id: Option<AccountId>,
+        baseline_date: DateTime<Utc>,
+    ) -> Result<Money, SendMoneyError> {
+        ensure_within_threshold(
+            self.exchange_rate_port.as_ref(),
+            self.money_transfer_properties.as_ref(),
+            *leg.money(),
+        )
+        .await?;
+
+        let target_id = *leg.target_account_id();
+        if let Entry::Vacant(entry) = accounts.entry(target_id) {
+            entry.insert(uow.load_account(target_id, baseline_date).await?);
+        }
+
+        if fee_account_id.is_none() {
+            return Ok(Money::zero(leg.money().currency()));
+        }
+        let source_id = *source_account.id().ok_or(AccountError::MissingAccountId)?;
+        let context = FeeContext::new(source_id, target_id)
+            .with_same_owner(source_account.has_same_owner(&accounts[&target_id]));
+
+        Ok(self
+            .money_transfer_properties
+            .fee_policy()
+            .fee(*leg.money(), &context)?)
+    }
+
+    async fn send_leg(
+        &self,
+        source_account: &mut Account,
+        accounts: &mut HashMap<AccountId, Account>,
+        leg: &TransferLeg,
+        fee: Money,
+        fee_account_id: Option<AccountId>,
+    ) -> Result<(), SendMoneyError> {
+        let target_account = accounts
+            .get_mut(leg.target_account_id())
+            .expect("Target accounts are loaded by prepare_leg");
+        move_money(
+            self.exchange_rate_port.as_ref(),
+            self.id_generator.as_ref(),
+            source_account,
+            target_account,
+            *leg.money(),
+        )
+        .await?;
+
+        if let Some(fee_account_id) = fee_account_id.filter(|_| fee.is_positive()) {
+            let fee_account = accounts
+                .get_mut(&fee_account_id)
+                .expect("The fee account is loaded when fees are charged");
+            move_money(
+                self.exchange_rate_port.as_ref(),
+                self.id_generator.as_ref(),
+                source_account,
+                fee_account,
+                fee,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Keep the failure of a single leg as its result, but abort the batch on persistence
+    /// errors.
+    fn leg_result<T>(
+        result: Result<T, SendMoneyError>,
+    ) -> Result<Result<T, SendMoneyError>, SendMoneyError> {
+        match result {
+            Err(err @ SendMoneyError::Persistence(_)) => Err(err),
+            result => Ok(result),
+        }
+    }
+
+    fn outcome<T>(
+        cmd: &SendMoneyBatchCommand,
+        results: Vec<Result<T, SendMoneyError>>,
+    ) -> BatchTransferOutcome {
+        let failed = results.iter().any(Result::is_err);
+
+        BatchTransferOutcome::new(
+            cmd.legs()
+                .iter()
+                .zip(results)
+                .map(|(leg, result)| {
+                    let status = match result {
+                        Err(err) => LegStatus::Failed(err),
+                        Ok(_) if failed => LegStatus::RolledBack,
+                        Ok(_) => LegStatus::Completed,
+                    };
+                    LegOutcome::new(*leg, status)
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use rocket::tokio;
+
+    use crate::{
+        adapter::output::StaticExchangeRateAdapter,
+        application::{
+            port::input::SendMoneyCommand,
+            tests::{MockIdGenerator, MockUnitOfWorkPort},
+            MoneyTransferPropertiesImpl,
+        },
+        domain::{fee::FlatFee, money::tests::eur},
+    };
+
+    use super::*;
+
+    fn service_with(
+        balances: &[(AccountId, Money)],
+        properties: MoneyTransferPropertiesImpl,
+    ) -> (SendMoneyBatchService, Arc<MockUnitOfWorkPort>) {
+        let uow_port = Arc::new(MockUnitOfWorkPort::with_balances(balances));
+        let service = SendMoneyBatchService {
+            unit_of_work_port: uow_port.clone(),
+            exchange_rate_port: Arc::new(StaticExchangeRateAdapter::new(vec![])),
+            money_transfer_properties: Arc::new(properties),
+            id_generator: Arc::new(MockIdGenerator::default()),
+        };
+
+        (service, uow_port)
+    }
+
+    fn service_with_balances(
+        balances: &[(AccountId, Money)],
+    ) -> (SendMoneyBatchService, Arc<MockUnitOfWorkPort>) {
+        service_with(balances, MoneyTransferPropertiesImpl::new(eur(1000)))
+    }
+
+    #[tokio::test]
+    async fn every_leg_is_committed() -> Result<()> {
+        // Given
+        let source_id = AccountId(41);
+        let (service, uow_port) = service_with_balances(&[
+            (source_id, eur(500)),
+            (AccountId(42), eur(0)),
+            (AccountId(43), eur(0)),
+        ]);
+        let cmd = SendMoneyBatchCommand::try_new(
+            source_id,
+            vec![(AccountId(43), eur(200)), (AccountId(42), eur(300))],
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?;
+
+        // When
+        let outcome = service.send_money_batch(cmd).await?;
+
+        // Expect
+        assert!(outcome.is_committed());
+        assert_eq!(outcome.legs().len(), 2);
+        assert_eq!(*outcome.legs()[0].leg().target_account_id(), AccountId(43));
+
+        let state = uow_port.state();
+        assert_eq!(state.committed.len(), 3);
+
+        let (updated_source, source_activities) = &state.committed[0];
+        assert_eq!(*updated_source, source_id);
+        assert_eq!(source_activities.len(), 2);
+        assert_eq!(*source_activities[0].money(), eur(200));
+        assert_eq!(*source_activities[1].money(), eur(300));
+
+        let updated: Vec<_> = state.committed[1..].iter().map(|(id, _)| *id).collect();
+        assert_eq!(updated, vec![AccountId(42), AccountId(43)]);
+
+        assert_eq!(state.locked, vec![source_id, AccountId(42), AccountId(43)]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_a_leg_fails_then_the_batch_is_rolled_back() -> Result<()> {
+        // Given
+        let source_id = AccountId(41);
+        let (service, uow_port) =
+            service_with_balances(&[(source_id, eur(500)), (AccountId(42), eur(0))]);
+        let cmd = SendMoneyBatchCommand::try_new(
+            source_id,
+            vec![(AccountId(42), eur(100)), (AccountId(43), eur(100))],
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?;
+
+        // When
+        let outcome = service.send_money_batch(cmd).await?;
+
+        // Expect
+        assert!(!outcome.is_committed());
+        assert!(matches!(outcome.legs()[0].status(), LegStatus::RolledBack));
+        assert!(matches!(
+            outcome.legs()[1].status(),
+            LegStatus::Failed(SendMoneyError::AccountNotFound(AccountId(43)))
+        ));
+
+        let state = uow_port.state();
+        assert!(state.committed.is_empty());
+        assert_eq!(state.rollbacks, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_total_exceeds_balance_then_nothing_is_updated() -> Result<()> {
+        // Given
+        let source_id = AccountId(41);
+        let (service, uow_port) = service_with_balances(&[
+            (source_id, eur(500)),
+            (AccountId(42), eur(0)),
+            (AccountId(43), eur(0)),
+        ]);
+        let cmd = SendMoneyBatchCommand::try_new(
+            source_id,
+            vec![(AccountId(42), eur(300)), (AccountId(43), eur(300))],
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?;
+
+        // When
+        let result = service.send_money_batch(cmd).await;
+
+        // Expect
+        assert!(matches!(
+            result,
+            Err(SendMoneyError::Account(
+                AccountError::InsufficientFunds { .. }
+            ))
+        ));
+        let state = uow_port.state();
+        assert!(state.committed.is_empty());
+        assert_eq!(state.rollbacks, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn fees_of_every_leg_are_transferred_to_fee_account() -> Result<()> {
+        // Given
+        let source_id = AccountId(41);
+        let fee_account_id = AccountId(99);
+        let properties = MoneyTransferPropertiesImpl::new(eur(1000))
+            .with_fees(Box::new(FlatFee::new(eur(2))), fee_account_id);
+        let (service, uow_port) = service_with(
+            &[
+                (source_id, eur(500)),
+                (AccountId(42), eur(0)),
+                (AccountId(43), eur(0)),
+                (fee_account_id, eur(0)),
+            ],
+            properties,
+        );
+        let cmd = SendMoneyBatchCommand::try_new(
+            source_id,
+            vec![(AccountId(42), eur(200)), (AccountId(43), eur(200))],
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?;
+
+        // When
+        service.send_money_batch(cmd).await?;
+
+        // Expect
+        let state = uow_port.state();
+        assert_eq!(state.committed.len(), 4);
+
+        let (_, source_activities) = &state.committed[0];
+        assert_eq!(source_activities.len(), 4);
+
+        let (updated_fee_account, fee_activities) = &state.committed[3];
+        assert_eq!(*updated_fee_account, fee_account_id);
+        assert_eq!(fee_activities.len(), 2);
+        assert!(fee_activities
+            .iter()
+            .all(|activity| *activity.money() == eur(2)));
+
+        assert!(state.locked.contains(&fee_account_id));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_fees_exceed_balance_then_nothing_is_updated() -> Result<()> {
+        // Given
+        let source_id = AccountId(41);
+        let fee_account_id = AccountId(99);
+        let properties = MoneyTransferPropertiesImpl::new(eur(1000))
+            .with_fees(Box::new(FlatFee::new(eur(2))), fee_account_id);
+        let (service, uow_port) = service_with(
+            &[
+                (source_id, eur(400)),
+                (AccountId(42), eur(0)),
+                (AccountId(43), eur(0)),
+                (fee_account_id, eur(0)),
+            ],
+            properties,
+        );
+        let cmd = SendMoneyBatchCommand::try_new(
+            source_id,
+            vec![(AccountId(42), eur(200)), (AccountId(43), eur(200))],
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?;
+
+        // When
+        let result = service.send_money_batch(cmd).await;
+
+        // Expect
+        assert!(matches!(
+            result,
+            Err(SendMoneyError::Account(
+                AccountError::InsufficientFunds { .. }
+            ))
+        ));
+        assert!(uow_port.state().committed.is_empty());
+        Ok(())
+    }
+}
@@ -1,14 +1,836 @@
-use anyhow::Result;
+use std::sync::Arc;
 
-use super::port::input::{SendMoneyCommand, SendMoneyUseCase};
+use chrono::{Duration, Utc};
+
+use crate::domain::{
+    account::{Account, AccountError},
+    fee::FeeContext,
+    money::Money,
+};
+
+use super::{
+    money_transfer_properties::MoneyTransferProperties,
+    port::{
+        input::{SendMoneyCommand, SendMoneyError, SendMoneyUseCase},
+        output::{
+            ExchangeRateError, ExchangeRatePort, IdGenerator, IdempotencyKey, IdempotencyRecord,
+            UnitOfWork, UnitOfWorkPort,
+        },
+    },
+    BASELINE_WINDOW_DAYS,
+};
 
 #[derive(Component)]
 #[shaku(interface = SendMoneyUseCase)]
-pub struct SendMoneyService;
+pub struct SendMoneyService {
+    #[shaku(inject)]
+    unit_of_work_port: Arc<dyn UnitOfWorkPort>,
+    #[shaku(inject)]
+    exchange_rate_port: Arc<dyn ExchangeRatePort>,
+    #[shaku(inject)]
+    money_transfer_properties: Arc<dyn MoneyTransferProperties>,
+    #[shaku(inject)]
+    id_generator: Arc<dyn IdGenerator>,
+}
 
 #[rocket::async_trait]
 impl SendMoneyUseCase for SendMoneyService {
-    async fn send_money(&self, _cmd: SendMoneyCommand) -> Result<bool> {
-        todo!()
+    async fn send_money(&self, cmd: SendMoneyCommand) -> Result<(), SendMoneyError> {
+        ensure_within_threshold(
+            self.exchange_rate_port.as_ref(),
+            self.money_transfer_properties.as_ref(),
+            *cmd.money(),
+        )
+        .await?;
+
+        self.transfer(&cmd).await
+    }
+}
+
+impl SendMoneyService {
+    /// Move money between accounts within a single unit of work, rolling it back on failure.
+    async fn transfer(&self, cmd: &SendMoneyCommand) -> Result<(), SendMoneyError> {
+        let mut uow = self.unit_of_work_port.begin().await?;
+
+        match self.lock_and_transfer_within(uow.as_mut(), cmd).await {
+            Ok(()) => Ok(uow.commit().await?),
+            Err(err) => {
+                if let Err(rollback_err) = uow.rollback().await {
+                    log::error!("Unable to roll back transfer: {}", rollback_err);
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// Lock the accounts involved in `cmd` for the rest of the unit of work, then transfer money,
+    /// once per idempotency key if any.
+    async fn lock_and_transfer_within(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        cmd: &SendMoneyCommand,
+    ) -> Result<(), SendMoneyError> {
+        let mut account_ids = vec![*cmd.source_account_id(), *cmd.target_account_id()];
+        account_ids.extend(self.money_transfer_properties.fee_account_id());
+        // Repeated commands lock the same accounts, so the first one completes before the next
+        // looks up its idempotency key
+        uow.lock_accounts(&account_ids).await?;
+
+        match cmd.idempotency_key() {
+            Some(key) => self.transfer_once_within(uow, key, cmd).await,
+            None => self.transfer_within(uow, cmd).await,
+        }
+    }
+
+    /// Transfer money unless a command with the same `key` was sent from the same account within
+    /// the retention window, in which case its result is returned again.
+    ///
+    /// The key is recorded by the unit of work moving the money, so it is remembered exactly when
+    /// the transfer is committed.
+    async fn transfer_once_within(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        key: &IdempotencyKey,
+        cmd: &SendMoneyCommand,
+    ) -> Result<(), SendMoneyError> {
+        let now = Utc::now();
+        let since = now - self.money_transfer_properties.idempotency_retention();
+
+        let record = uow
+            .find_idempotency_record(*cmd.source_account_id(), key, since)
+            .await?;
+        if let Some(record) = record {
+            return if record.matches(cmd.target_account_id(), cmd.money()) {
+                Ok(())
+            } else {
+                Err(SendMoneyError::IdempotencyKeyReused(key.clone()))
+            };
+        }
+
+        self.transfer_within(uow, cmd).await?;
+
+        let record = IdempotencyRecord::new(
+            key.clone(),
+            *cmd.source_account_id(),
+            *cmd.target_account_id(),
+            *cmd.money(),
+            now,
+        );
+        Ok(uow.save_idempotency_record(&record, since).await?)
+    }
+
+    async fn transfer_within(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        cmd: &SendMoneyCommand,
+    ) -> Result<(), SendMoneyError> {
+        let baseline_date = Utc::now() - Duration::days(BASELINE_WINDOW_DAYS);
+
+        let mut source_account = uow
+            .load_account(*cmd.source_account_id(), baseline_date)
+            .await?;
+        let mut target_account = uow
+            .load_account(*cmd.target_account_id(), baseline_date)
+            .await?;
+
+        let money = *cmd.money();
+        let context = FeeContext::new(*cmd.source_account_id(), *cmd.target_account_id())
+            .with_same_owner(source_account.has_same_owner(&target_account));
+        let fee = self
+            .money_transfer_properties
+            .fee_policy()
+            .fee(money, &context)?;
+        let fee_account_id = self
+            .money_transfer_properties
+            .fee_account_id()
+            .filter(|id| fee.is_positive() && id != cmd.source_account_id());
+
+        if fee_account_id.is_some() {
+            // The fee is charged on top of the transferred money
+            source_account.ensure_may_withdraw((money + fee)?)?;
+        }
+        move_money(
+            self.exchange_rate_port.as_ref(),
+            self.id_generator.as_ref(),
+            &mut source_account,
+            &mut target_account,
+            money,
+        )
+        .await?;
+
+        match fee_account_id {
+            None => {
+                uow.update_activities(&source_account).await?;
+                uow.update_activities(&target_account).await?;
+            }
+            Some(fee_account_id) if fee_account_id == *cmd.target_account_id() => {
+                move_money(
+                    self.exchange_rate_port.as_ref(),
+                    self.id_generator.as_ref(),
+                    &mut source_account,
+                    &mut target_account,
+                    fee,
+                )
+                .await?;
+                uow.update_activities(&source_account).await?;
+                uow.update_activities(&target_account).await?;
+            }
+            Some(fee_account_id) => {
+                let mut fee_account = uow.load_account(fee_account_id, baseline_date).await?;
+                move_money(
+                    self.exchange_rate_port.as_ref(),
+                    self.id_generator.as_ref(),
+                    &mut source_account,
+                    &mut fee_account,
+                    fee,
+                )
+                .await?;
+                uow.update_activities(&source_account).await?;
+                uow.update_activities(&target_account).await?;
+                uow.update_activities(&fee_account).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Fail unless `money` does not exceed the maximum transfer threshold, once converted into the
+/// threshold currency.
+///
+/// Amounts without any rate into the threshold currency are not checked, their transfer needing
+/// no such rate: they are bounded by the command ceiling only.
+pub(super) async fn ensure_within_threshold(
+    exchange_rate_port: &dyn ExchangeRatePort,
+    money_transfer_properties: &dyn MoneyTransferProperties,
+    money: Money,
+) -> Result<(), SendMoneyError> {
+    let threshold = money_transfer_properties.maximum_transfer_threshold();
+    let rate = match exchange_rate_port
+        .exchange_rate(money.currency(), threshold.currency())
+        .await
+    {
+        Ok(rate) => rate,
+        Err(ExchangeRateError::Unavailable { .. }) => return Ok(()),
+    };
+    let actual = rate.convert(money)?;
+    if actual > threshold {
+        return Err(SendMoneyError::ThresholdExceeded { threshold, actual });
+    }
+
+    Ok(())
+}
+
+/// Withdraw `money` from `source` and deposit it into `target`, converting it to the currency
+/// of `target` when needed. Both activities record a new transfer.
+pub(super) async fn move_money(
+    exchange_rate_port: &dyn ExchangeRatePort,
+    id_generator: &dyn IdGenerator,
+    source: &mut Account,
+    target: &mut Account,
+    money: Money,
+) -> Result<(), SendMoneyError> {
+    let source_id = *source.id().ok_or(AccountError::MissingAccountId)?;
+    let target_id = *target.id().ok_or(AccountError::MissingAccountId)?;
+
+    let converted_money = if source.currency() == target.currency() {
+        None
+    } else {
+        let converted_money = exchange_rate_port
+            .exchange_rate(source.currency(), target.currency())
+            .await?
+            .convert(money)?;
+        Some(converted_money)
+    };
+
+    let transfer_id = Some(id_generator.next_transfer_id().await?);
+    source.withdraw_converted(money, converted_money, target_id, transfer_id)?;
+    target.deposit_converted(money, converted_money, source_id, transfer_id)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use rocket::tokio;
+
+    use crate::{
+        adapter::output::StaticExchangeRateAdapter,
+        application::{
+            tests::{MockIdGenerator, MockUnitOfWorkPort},
+            MoneyTransferPropertiesImpl,
+        },
+        domain::{
+            account::AccountId,
+            customer::CustomerId,
+            exchange_rate::ExchangeRate,
+            fee::{FlatFee, FreeWithinSameOwner},
+            money::{tests::eur, Currency, Money},
+        },
+    };
+
+    use super::*;
+
+    fn service_with(uow_port: MockUnitOfWorkPort) -> (SendMoneyService, Arc<MockUnitOfWorkPort>) {
+        let uow_port = Arc::new(uow_port);
+        let service = SendMoneyService {
+            unit_of_work_port: uow_port.clone(),
+            exchange_rate_port: Arc::new(StaticExchangeRateAdapter::new(vec![eur_usd()])),
+            money_transfer_properties: Arc::new(MoneyTransferPropertiesImpl::new(eur(1000))),
+            id_generator: Arc::new(MockIdGenerator::default()),
+        };
+
+        (service, uow_port)
+    }
+
+    /// EUR to USD rate of 1.085, the only conversion available to tests.
+    fn eur_usd() -> ExchangeRate {
+        ExchangeRate::from_decimal_str(Currency::EUR, Currency::USD, "1.085").unwrap()
+    }
+
+    fn usd(amount: i64) -> Money {
+        Money::new(amount, Currency::USD)
+    }
+
+    fn service_with_balances(
+        balances: &[(AccountId, Money)],
+    ) -> (SendMoneyService, Arc<MockUnitOfWorkPort>) {
+        service_with(MockUnitOfWorkPort::with_balances(balances))
+    }
+
+    #[tokio::test]
+    async fn transaction_succeeds() -> Result<()> {
+        // Given
+        let source_id = AccountId(41);
+        let target_id = AccountId(42);
+        let (service, uow_port) =
+            service_with_balances(&[(source_id, eur(500)), (target_id, eur(0))]);
+        let cmd = SendMoneyCommand::try_new(
+            source_id,
+            target_id,
+            eur(300),
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?;
+
+        // When
+        service.send_money(cmd).await?;
+
+        // Expect
+        let state = uow_port.state();
+        assert_eq!(state.committed.len(), 2);
+
+        let (updated_source, source_activities) = &state.committed[0];
+        assert_eq!(*updated_source, source_id);
+        assert_eq!(source_activities.len(), 1);
+        assert_eq!(*source_activities[0].owner_account_id(), source_id);
+        assert_eq!(*source_activities[0].target_account_id(), target_id);
+        assert_eq!(*source_activities[0].money(), eur(300));
+
+        let (updated_target, target_activities) = &state.committed[1];
+        assert_eq!(*updated_target, target_id);
+        assert_eq!(target_activities.len(), 1);
+        assert_eq!(*target_activities[0].owner_account_id(), target_id);
+        assert_eq!(*target_activities[0].source_account_id(), source_id);
+        assert_eq!(*target_activities[0].money(), eur(300));
+        assert!(target_activities[0].transfer_id().is_some());
+        assert_eq!(
+            target_activities[0].transfer_id(),
+            source_activities[0].transfer_id()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn repeated_commands_are_sent_once() -> Result<()> {
+        // Given
+        let source_id = AccountId(41);
+        let target_id = AccountId(42);
+        let (service, uow_port) =
+            service_with_balances(&[(source_id, eur(500)), (target_id, eur(0))]);
+        let cmd = || {
+            SendMoneyCommand::try_new(
+                source_id,
+                target_id,
+                eur(300),
+                SendMoneyCommand::DEFAULT_CEILING,
+            )?
+            .with_idempotency_key("transfer-1")
+        };
+
+        // When
+        service.send_money(cmd()?).await?;
+        service.send_money(cmd()?).await?;
+
+        // Expect
+        assert_eq!(uow_port.state().committed.len(), 2);
+        assert_eq!(uow_port.idempotency_records().len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn idempotency_keys_are_scoped_to_source_accounts() -> Result<()> {
+        // Given
+        let target_id = AccountId(43);
+        let (service, uow_port) = service_with_balances(&[
+            (AccountId(41), eur(500)),
+            (AccountId(42), eur(500)),
+            (target_id, eur(0)),
+        ]);
+        let cmd = |source_id| {
+            SendMoneyCommand::try_new(
+                source_id,
+                target_id,
+                eur(300),
+                SendMoneyCommand::DEFAULT_CEILING,
+            )?
+            .with_idempotency_key("transfer-1")
+        };
+
+        // When
+        service.send_money(cmd(AccountId(41))?).await?;
+        service.send_money(cmd(AccountId(42))?).await?;
+
+        // Expect
+        assert_eq!(uow_port.state().committed.len(), 4);
+        assert_eq!(uow_port.idempotency_records().len(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_transfer_fails_then_idempotency_key_is_not_recorded() -> Result<()> {
+        // Given
+        let source_id = AccountId(41);
+        let target_id = AccountId(42);
+        let (service, uow_port) = service_with(
+            MockUnitOfWorkPort::with_balances(&[(source_id, eur(500)), (target_id, eur(0))])
+                .failing_updates_of(target_id),
+        );
+        let cmd = SendMoneyCommand::try_new(
+            source_id,
+            target_id,
+            eur(300),
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?
+        .with_idempotency_key("transfer-1")?;
+
+        // When
+        let result = service.send_money(cmd).await;
+
+        // Expect
+        assert!(matches!(result, Err(SendMoneyError::Persistence(_))));
+        assert!(uow_port.idempotency_records().is_empty());
+        assert_eq!(uow_port.state().rollbacks, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn idempotency_keys_of_other_transfers_are_rejected() -> Result<()> {
+        // Given
+        let source_id = AccountId(41);
+        let target_id = AccountId(42);
+        let (service, uow_port) =
+            service_with_balances(&[(source_id, eur(500)), (target_id, eur(0))]);
+        let first = SendMoneyCommand::try_new(
+            source_id,
+            target_id,
+            eur(300),
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?
+        .with_idempotency_key("transfer-1")?;
+        let second = SendMoneyCommand::try_new(
+            source_id,
+            target_id,
+            eur(200),
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?
+        .with_idempotency_key("transfer-1")?;
+
+        // When
+        service.send_money(first).await?;
+        let result = service.send_money(second).await;
+
+        // Expect
+        assert!(matches!(
+            result,
+            Err(SendMoneyError::IdempotencyKeyReused(IdempotencyKey(key))) if key == "transfer-1"
+        ));
+        assert_eq!(uow_port.state().committed.len(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_withdrawal_fails_then_nothing_is_updated() -> Result<()> {
+        // Given
+        let source_id = AccountId(41);
+        let target_id = AccountId(42);
+        let (service, uow_port) =
+            service_with_balances(&[(source_id, eur(100)), (target_id, eur(0))]);
+        let cmd = SendMoneyCommand::try_new(
+            source_id,
+            target_id,
+            eur(300),
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?;
+
+        // When
+        let result = service.send_money(cmd).await;
+
+        // Expect
+        assert!(matches!(
+            result,
+            Err(SendMoneyError::Account(
+                AccountError::InsufficientFunds { .. }
+            ))
+        ));
+        assert!(uow_port.state().committed.is_empty());
+        assert_eq!(uow_port.state().rollbacks, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_deposit_update_fails_then_withdrawal_is_rolled_back() -> Result<()> {
+        // Given
+        let source_id = AccountId(41);
+        let target_id = AccountId(42);
+        let (service, uow_port) = service_with(
+            MockUnitOfWorkPort::with_balances(&[(source_id, eur(500)), (target_id, eur(0))])
+                .failing_updates_of(target_id),
+        );
+        let cmd = SendMoneyCommand::try_new(
+            source_id,
+            target_id,
+            eur(300),
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?;
+
+        // When
+        let result = service.send_money(cmd).await;
+
+        // Expect
+        assert!(matches!(result, Err(SendMoneyError::Persistence(_))));
+        assert!(uow_port.state().committed.is_empty());
+        assert_eq!(uow_port.state().rollbacks, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_threshold_exceeded_then_nothing_is_updated() -> Result<()> {
+        // Given
+        let source_id = AccountId(41);
+        let target_id = AccountId(42);
+        let (service, uow_port) =
+            service_with_balances(&[(source_id, eur(5000)), (target_id, eur(0))]);
+        let cmd = SendMoneyCommand::try_new(
+            source_id,
+            target_id,
+            eur(1001),
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?;
+
+        // When
+        let result = service.send_money(cmd).await;
+
+        // Expect
+        assert!(matches!(
+            result,
+            Err(SendMoneyError::ThresholdExceeded {
+                threshold,
+                actual,
+            }) if threshold == eur(1000) && actual == eur(1001)
+        ));
+        assert!(uow_port.state().committed.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn transaction_between_currencies_succeeds() -> Result<()> {
+        // Given
+        let source_id = AccountId(41);
+        let target_id = AccountId(42);
+        let (service, uow_port) =
+            service_with_balances(&[(source_id, eur(500)), (target_id, usd(0))]);
+        let cmd = SendMoneyCommand::try_new(
+            source_id,
+            target_id,
+            eur(300),
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?;
+
+        // When
+        service.send_money(cmd).await?;
+
+        // Expect
+        let state = uow_port.state();
+        assert_eq!(state.committed.len(), 2);
+        for (_, activities) in &state.committed {
+            assert_eq!(activities.len(), 1);
+            assert_eq!(*activities[0].money(), eur(300));
+            assert_eq!(activities[0].converted_money(), Some(&usd(326)));
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_no_exchange_rate_then_nothing_is_updated() -> Result<()> {
+        // Given
+        let source_id = AccountId(41);
+        let target_id = AccountId(42);
+        let (service, uow_port) =
+            service_with_balances(&[(source_id, usd(500)), (target_id, eur(0))]);
+        let cmd = SendMoneyCommand::try_new(
+            source_id,
+            target_id,
+            usd(300),
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?;
+
+        // When
+        let result = service.send_money(cmd).await;
+
+        // Expect
+        assert!(matches!(
+            result,
+            Err(SendMoneyError::ExchangeRate(
+                ExchangeRateError::Unavailable { .. }
+            ))
+        ));
+        assert!(uow_port.state().committed.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn transaction_in_other_currency_than_threshold_needs_no_rate() -> Result<()> {
+        // Given
+        let source_id = AccountId(41);
+        let target_id = AccountId(42);
+        let (service, uow_port) =
+            service_with_balances(&[(source_id, usd(500)), (target_id, usd(0))]);
+        let service = SendMoneyService {
+            exchange_rate_port: Arc::new(StaticExchangeRateAdapter::new(vec![])),
+            ..service
+        };
+        let cmd = SendMoneyCommand::try_new(
+            source_id,
+            target_id,
+            usd(300),
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?;
+
+        // When
+        service.send_money(cmd).await?;
+
+        // Expect
+        let state = uow_port.state();
+        assert_eq!(state.committed.len(), 2);
+        for (_, activities) in &state.committed {
+            assert_eq!(*activities[0].money(), usd(300));
+            assert_eq!(activities[0].converted_money(), None);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn threshold_applies_to_converted_amount() -> Result<()> {
+        // Given
+        let (service, _) = service_with_balances(&[]);
+        let service = SendMoneyService {
+            money_transfer_properties: Arc::new(MoneyTransferPropertiesImpl::new(usd(100_000))),
+            ..service
+        };
+        // 922 EUR = 1000.37 USD
+        let cmd = SendMoneyCommand::try_new(
+            AccountId(41),
+            AccountId(42),
+            eur(92_200),
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?;
+
+        // When
+        let result = service.send_money(cmd).await;
+
+        // Expect
+        assert!(matches!(
+            result,
+            Err(SendMoneyError::ThresholdExceeded { threshold, actual })
+                if threshold == usd(100_000) && actual == usd(100_037)
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn accounts_are_locked_in_order() -> Result<()> {
+        // Given
+        let source_id = AccountId(42);
+        let target_id = AccountId(41);
+        let (service, uow_port) =
+            service_with_balances(&[(source_id, eur(500)), (target_id, eur(0))]);
+        let cmd = SendMoneyCommand::try_new(
+            source_id,
+            target_id,
+            eur(300),
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?;
+
+        // When
+        service.send_money(cmd).await?;
+
+        // Expect
+        assert_eq!(uow_port.state().locked, vec![target_id, source_id]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_unknown_account_then_send_money_fails() -> Result<()> {
+        // Given
+        let source_id = AccountId(41);
+        let (service, uow_port) = service_with_balances(&[(source_id, eur(500))]);
+        let cmd = SendMoneyCommand::try_new(
+            source_id,
+            AccountId(99),
+            eur(300),
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?;
+
+        // When
+        let result = service.send_money(cmd).await;
+
+        // Expect
+        assert!(matches!(
+            result,
+            Err(SendMoneyError::AccountNotFound(AccountId(99)))
+        ));
+        assert!(uow_port.state().committed.is_empty());
+        assert_eq!(uow_port.state().rollbacks, 1);
+        Ok(())
+    }
+
+    fn service_with_fees(
+        balances: &[(AccountId, Money)],
+    ) -> (SendMoneyService, Arc<MockUnitOfWorkPort>) {
+        let (service, uow_port) = service_with_balances(balances);
+        let properties = MoneyTransferPropertiesImpl::new(eur(1000))
+            .with_fees(Box::new(FlatFee::new(eur(2))), AccountId(99));
+        let service = SendMoneyService {
+            money_transfer_properties: Arc::new(properties),
+            ..service
+        };
+
+        (service, uow_port)
+    }
+
+    #[tokio::test]
+    async fn fee_is_transferred_to_fee_account() -> Result<()> {
+        // Given
+        let source_id = AccountId(41);
+        let target_id = AccountId(42);
+        let fee_account_id = AccountId(99);
+        let (service, uow_port) = service_with_fees(&[
+            (source_id, eur(500)),
+            (target_id, eur(0)),
+            (fee_account_id, eur(0)),
+        ]);
+        let cmd = SendMoneyCommand::try_new(
+            source_id,
+            target_id,
+            eur(300),
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?;
+
+        // When
+        service.send_money(cmd).await?;
+
+        // Expect
+        let state = uow_port.state();
+        assert_eq!(state.committed.len(), 3);
+
+        let (_, source_activities) = &state.committed[0];
+        assert_eq!(source_activities.len(), 2);
+        assert_eq!(*source_activities[0].money(), eur(300));
+        assert_eq!(*source_activities[1].target_account_id(), fee_account_id);
+        assert_eq!(*source_activities[1].money(), eur(2));
+        assert_ne!(
+            source_activities[1].transfer_id(),
+            source_activities[0].transfer_id()
+        );
+
+        let (updated_fee_account, fee_activities) = &state.committed[2];
+        assert_eq!(*updated_fee_account, fee_account_id);
+        assert_eq!(fee_activities.len(), 1);
+        assert_eq!(*fee_activities[0].source_account_id(), source_id);
+        assert_eq!(*fee_activities[0].money(), eur(2));
+
+        assert!(state.locked.contains(&fee_account_id));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_fee_exceeds_balance_then_nothing_is_updated() -> Result<()> {
+        // Given
+        let source_id = AccountId(41);
+        let target_id = AccountId(42);
+        let (service, uow_port) = service_with_fees(&[
+            (source_id, eur(300)),
+            (target_id, eur(0)),
+            (AccountId(99), eur(0)),
+        ]);
+        let cmd = SendMoneyCommand::try_new(
+            source_id,
+            target_id,
+            eur(300),
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?;
+
+        // When
+        let result = service.send_money(cmd).await;
+
+        // Expect
+        assert!(matches!(
+            result,
+            Err(SendMoneyError::Account(AccountError::InsufficientFunds { requested, .. }))
+                if requested == eur(302)
+        ));
+        assert!(uow_port.state().committed.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn given_accounts_of_same_owner_then_fee_is_waived() -> Result<()> {
+        // Given
+        let source_id = AccountId(41);
+        let target_id = AccountId(42);
+        let (service, _) = service_with_balances(&[]);
+        let uow_port = Arc::new(
+            MockUnitOfWorkPort::with_balances(&[
+                (source_id, eur(500)),
+                (target_id, eur(0)),
+                (AccountId(99), eur(0)),
+            ])
+            .owned_by(&[source_id, target_id], CustomerId(1)),
+        );
+        let properties = MoneyTransferPropertiesImpl::new(eur(1000)).with_fees(
+            Box::new(FreeWithinSameOwner::new(Box::new(FlatFee::new(eur(2))))),
+            AccountId(99),
+        );
+        let service = SendMoneyService {
+            unit_of_work_port: uow_port.clone(),
+            money_transfer_properties: Arc::new(properties),
+            ..service
+        };
+        let cmd = SendMoneyCommand::try_new(
+            source_id,
+            target_id,
+            eur(500),
+            SendMoneyCommand::DEFAULT_CEILING,
+        )?;
+
+        // When
+        service.send_money(cmd).await?;
+
+        // Expect
+        let state = uow_port.state();
+        assert_eq!(state.committed.len(), 2);
+        assert_eq!(state.committed[0].1.len(), 1);
+        Ok(())
     }
 }
@@ -0,0 +1,416 @@
+//! Mock output ports shared by application services tests.
+
+use std::{collections::HashMap, sync::Arc};
+
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+
+use crate::{
+    adapter::output::{InMemoryIdempotencyRecords, InMemoryIdempotencyStore},
+    application::port::output::{
+        AccountLock, CreateAccountPort, IdGenerator, IdempotencyKey, IdempotencyRecord,
+        IdempotencyStore, LoadAccountPort, LoadActivityPort, PersistenceError,
+        ScheduledTransferPort, UnitOfWork, UnitOfWorkPort,
+    },
+    domain::{
+        account::{tests::default_account, Account, AccountId, AccountStatus},
+        activity::{Activity, ActivityId, ActivityWindow, TransferId},
+        customer::CustomerId,
+        money::Money,
+        scheduled_transfer::{ScheduledTransfer, ScheduledTransferId},
+    },
+};
+
+/// State shared by a `MockUnitOfWorkPort` and the units of work it begins.
+#[derive(Debug, Default)]
+pub struct MockUnitOfWorkState {
+    /// Accounts locked by every unit of work, in locking order.
+    pub locked: Vec<AccountId>,
+    /// New activities of every account updated by committed units of work.
+    pub committed: Vec<(AccountId, Vec<Activity>)>,
+    /// Statuses updated by committed units of work.
+    pub statuses: Vec<(AccountId, AccountStatus)>,
+    pub rollbacks: usize,
+}
+
+/// `UnitOfWorkPort` loading accounts with an empty activity window and the given baseline
+/// balances.
+#[derive(Default)]
+pub struct MockUnitOfWorkPort {
+    balances: HashMap<AccountId, Money>,
+    owners: HashMap<AccountId, CustomerId>,
+    reversals: HashMap<ActivityId, ActivityId>,
+    transfers: Vec<Vec<ActivityId>>,
+    failing_account: Option<AccountId>,
+    idempotency_records: Arc<InMemoryIdempotencyRecords>,
+    state: Arc<Mutex<MockUnitOfWorkState>>,
+}
+
+impl MockUnitOfWorkPort {
+    pub fn with_balances(balances: &[(AccountId, Money)]) -> Self {
+        Self {
+            balances: balances.iter().cloned().collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Make `customer_id` the owner of `account_ids`.
+    pub fn owned_by(mut self, account_ids: &[AccountId], customer_id: CustomerId) -> Self {
+        self.owners.extend(
+            account_ids
+                .iter()
+                .map(|account_id| (*account_id, customer_id)),
+        );
+        self
+    }
+
+    /// Record `original` as already reversed by `reversal`.
+    pub fn with_reversal(mut self, original: ActivityId, reversal: ActivityId) -> Self {
+        self.reversals.insert(original, reversal);
+        self
+    }
+
+    /// Record `activity_ids` as recording the same transfer.
+    pub fn with_transfer(mut self, activity_ids: &[ActivityId]) -> Self {
+        self.transfers.push(activity_ids.to_vec());
+        self
+    }
+
+    /// Make updates of `account_id` fail.
+    pub fn failing_updates_of(mut self, account_id: AccountId) -> Self {
+        self.failing_account = Some(account_id);
+        self
+    }
+
+    pub fn state(&self) -> parking_lot::MutexGuard<'_, MockUnitOfWorkState> {
+        self.state.lock()
+    }
+
+    /// Get the idempotency records saved by committed units of work.
+    pub fn idempotency_records(&self) -> Vec<IdempotencyRecord> {
+        self.idempotency_records.records()
+    }
+}
+
+#[rocket::async_trait]
+impl UnitOfWorkPort for MockUnitOfWorkPort {
+    async fn begin(&self) -> Result<Box<dyn UnitOfWork>, PersistenceError> {
+        Ok(Box::new(MockUnitOfWork {
+            balances: self.balances.clone(),
+            owners: self.owners.clone(),
+            reversals: self.reversals.clone(),
+            transfers: self.transfers.clone(),
+            failing_account: self.failing_account,
+            staged: vec![],
+            staged_statuses: vec![],
+            idempotency_store: self.idempotency_records.store(),
+            state: self.state.clone(),
+        }))
+    }
+}
+
+pub struct MockUnitOfWork {
+    balances: HashMap<AccountId, Money>,
+    owners: HashMap<AccountId, CustomerId>,
+    reversals: HashMap<ActivityId, ActivityId>,
+    transfers: Vec<Vec<ActivityId>>,
+    failing_account: Option<AccountId>,
+    staged: Vec<(AccountId, Vec<Activity>)>,
+    staged_statuses: Vec<(AccountId, AccountStatus)>,
+    idempotency_store: InMemoryIdempotencyStore,
+    state: Arc<Mutex<MockUnitOfWorkState>>,
+}
+
+#[rocket::async_trait]
+impl AccountLock for MockUnitOfWork {
+    async fn lock_account(&mut self, account_id: AccountId) -> Result<(), PersistenceError> {
+        self.state.lock().locked.push(account_id);
+
+        Ok(())
+    }
+
+    async fn release_account(&mut self, _account_id: AccountId) -> Result<(), PersistenceError> {
+        Ok(())
+    }
+}
+
+#[rocket::async_trait]
+impl IdempotencyStore for MockUnitOfWork {
+    async fn find_idempotency_record(
+        &mut self,
+        source_account_id: AccountId,
+        key: &IdempotencyKey,
+        since: DateTime<Utc>,
+    ) -> Result<Option<IdempotencyRecord>, PersistenceError> {
+        self.idempotency_store
+            .find_idempotency_record(source_account_id, key, since)
+            .await
+    }
+
+    async fn save_idempotency_record(
+        &mut self,
+        record: &IdempotencyRecord,
+        since: DateTime<Utc>,
+    ) -> Result<(), PersistenceError> {
+        self.idempotency_store
+            .save_idempotency_record(record, since)
+            .await
+    }
+}
+
+#[rocket::async_trait]
+impl UnitOfWork for MockUnitOfWork {
+    async fn load_account(
+        &mut self,
+        account_id: AccountId,
+        _baseline_date: DateTime<Utc>,
+    ) -> Result<Account, PersistenceError> {
+        let balance = self
+            .balances
+            .get(&account_id)
+            .ok_or(PersistenceError::AccountNotFound(account_id))?;
+
+        Ok(default_account()
+            .id(account_id)
+            .baseline_balance(*balance)
+            .activity_window(ActivityWindow::new(vec![]))
+            .owner_id(self.owners.get(&account_id).copied())
+            .build()
+            .unwrap())
+    }
+
+    async fn update_activities(&mut self, account: &Account) -> Result<Account, PersistenceError> {
+        let account_id = *account.id().ok_or(PersistenceError::MissingAccountId)?;
+        if self.failing_account == Some(account_id) {
+            return Err(PersistenceError::CorruptedData("Mock failure".into()));
+        }
+
+        let activities = account.activity_window().activities().to_vec();
+        self.staged.push((account_id, activities));
+
+        Ok(account.clone())
+    }
+
+    async fn find_reversal(
+        &mut self,
+        activity_id: ActivityId,
+    ) -> Result<Option<ActivityId>, PersistenceError> {
+        let transfer = self
+            .transfers
+            .iter()
+            .find(|transfer| transfer.contains(&activity_id))
+            .cloned()
+            .unwrap_or_else(|| vec![activity_id]);
+
+        Ok(transfer
+            .iter()
+            .find_map(|activity_id| self.reversals.get(activity_id))
+            .copied())
+    }
+
+    async fn update_status(&mut self, account: &Account) -> Result<(), PersistenceError> {
+        let account_id = *account.id().ok_or(PersistenceError::MissingAccountId)?;
+        self.staged_statuses.push((account_id, account.status()));
+        Ok(())
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), PersistenceError> {
+        self.idempotency_store.commit()?;
+        let mut state = self.state.lock();
+        state.committed.extend(self.staged);
+        state.statuses.extend(self.staged_statuses);
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), PersistenceError> {
+        self.state.lock().rollbacks += 1;
+        Ok(())
+    }
+}
+
+/// `CreateAccountPort` keeping created accounts in memory.
+#[derive(Default)]
+pub struct MockCreateAccountPort {
+    existing: Vec<AccountId>,
+    created: Mutex<Vec<Account>>,
+}
+
+impl MockCreateAccountPort {
+    /// Make creation of any of `account_ids` fail as already taken.
+    pub fn with_existing(account_ids: &[AccountId]) -> Self {
+        Self {
+            existing: account_ids.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    pub fn created(&self) -> Vec<Account> {
+        self.created.lock().clone()
+    }
+}
+
+#[rocket::async_trait]
+impl CreateAccountPort for MockCreateAccountPort {
+    async fn create_account(&self, account: &Account) -> Result<(), PersistenceError> {
+        let account_id = *account.id().ok_or(PersistenceError::MissingAccountId)?;
+        if self.existing.contains(&account_id) {
+            return Err(PersistenceError::AccountAlreadyExists(account_id));
+        }
+
+        self.created.lock().push(account.clone());
+        Ok(())
+    }
+}
+
+/// `IdGenerator` counting up from `1`.
+#[derive(Default)]
+pub struct MockIdGenerator {
+    last_account_id: Mutex<u64>,
+    last_transfer_id: Mutex<u64>,
+}
+
+#[rocket::async_trait]
+impl IdGenerator for MockIdGenerator {
+    async fn next_account_id(&self) -> Result<AccountId, PersistenceError> {
+        let mut last_account_id = self.last_account_id.lock();
+        *last_account_id += 1;
+
+        Ok(AccountId(*last_account_id))
+    }
+
+    async fn next_transfer_id(&self) -> Result<TransferId, PersistenceError> {
+        let mut last_transfer_id = self.last_transfer_id.lock();
+        *last_transfer_id += 1;
+
+        Ok(TransferId(*last_transfer_id))
+    }
+}
+
+/// `ScheduledTransferPort` keeping transfers in memory, identified by their position.
+#[derive(Default)]
+pub struct MockScheduledTransferPort {
+    transfers: Mutex<Vec<ScheduledTransfer>>,
+    failing_outcome: Mutex<Option<ScheduledTransferId>>,
+}
+
+impl MockScheduledTransferPort {
+    pub fn with_transfers(transfers: Vec<ScheduledTransfer>) -> Self {
+        let transfers = transfers
+            .into_iter()
+            .enumerate()
+            .map(|(i, transfer)| transfer.with_id(ScheduledTransferId(i as u64)))
+            .collect();
+
+        Self {
+            transfers: Mutex::new(transfers),
+            ..Default::default()
+        }
+    }
+
+    /// Make recording the outcome of `id` fail, until `None` is given.
+    pub fn fail_outcomes_of(&self, id: Option<ScheduledTransferId>) {
+        *self.failing_outcome.lock() = id;
+    }
+
+    pub fn transfers(&self) -> Vec<ScheduledTransfer> {
+        self.transfers.lock().clone()
+    }
+}
+
+#[rocket::async_trait]
+impl ScheduledTransferPort for MockScheduledTransferPort {
+    async fn schedule(
+        &self,
+        transfer: &ScheduledTransfer,
+    ) -> Result<ScheduledTransferId, PersistenceError> {
+        let mut transfers = self.transfers.lock();
+        let id = ScheduledTransferId(transfers.len() as u64);
+        transfers.push(transfer.clone().with_id(id));
+
+        Ok(id)
+    }
+
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        claim_timeout: Duration,
+        limit: u32,
+    ) -> Result<Vec<ScheduledTransfer>, PersistenceError> {
+        let mut claimed = vec![];
+        for transfer in self.transfers.lock().iter_mut() {
+            let claimable = transfer.is_due(now) || transfer.is_claim_expired(now - claim_timeout);
+            if claimed.len() < limit as usize && claimable {
+                transfer.mark_executing(now);
+                claimed.push(transfer.clone());
+            }
+        }
+
+        Ok(claimed)
+    }
+
+    async fn record_outcome(&self, transfer: &ScheduledTransfer) -> Result<(), PersistenceError> {
+        let id = transfer.id().ok_or_else(|| {
+            PersistenceError::CorruptedData("Scheduled transfer id is not set".into())
+        })?;
+        if *self.failing_outcome.lock() == Some(*id) {
+            return Err(PersistenceError::CorruptedData("Mock failure".into()));
+        }
+        self.transfers.lock()[id.0 as usize] = transfer.clone();
+
+        Ok(())
+    }
+}
+
+/// `LoadAccountPort` loading default accounts with the given ids only.
+pub struct MockLoadAccountPort {
+    account_ids: Vec<AccountId>,
+}
+
+impl MockLoadAccountPort {
+    pub fn with_accounts(account_ids: &[AccountId]) -> Self {
+        Self {
+            account_ids: account_ids.to_vec(),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl LoadAccountPort for MockLoadAccountPort {
+    async fn load_account(
+        &self,
+        account_id: AccountId,
+        _baseline_date: DateTime<Utc>,
+    ) -> Result<Account, PersistenceError> {
+        if !self.account_ids.contains(&account_id) {
+            return Err(PersistenceError::AccountNotFound(account_id));
+        }
+
+        Ok(default_account().id(account_id).build().unwrap())
+    }
+}
+
+/// `LoadActivityPort` finding activities among the given ones.
+pub struct MockLoadActivityPort {
+    activities: Vec<Activity>,
+}
+
+impl MockLoadActivityPort {
+    pub fn with_activities(activities: Vec<Activity>) -> Self {
+        Self { activities }
+    }
+}
+
+#[rocket::async_trait]
+impl LoadActivityPort for MockLoadActivityPort {
+    async fn load_activity(&self, activity_id: ActivityId) -> Result<Activity, PersistenceError> {
+        self.activities
+            .iter()
+            .find(|activity| activity.id() == Some(&activity_id))
+            .cloned()
+            .ok_or(PersistenceError::ActivityNotFound(activity_id))
+    }
+
+    async fn load_activities(&self) -> Result<Vec<Activity>, PersistenceError> {
+        Ok(self.activities.clone())
+    }
+}
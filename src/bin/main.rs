@@ -1,20 +1,35 @@
+use std::{env, process};
+
+use rocket::{fairing::AdHoc, Build, Rocket};
 use rocket_hexagonal::{
-    adapter::input::rest,
-    infrastructure::container::{connect_db, default_module},
+    adapter::input::{cli, rest},
+    infrastructure::container,
 };
 
-#[rocket::launch]
-async fn rocket() -> _ {
-    let uri = format!(
-        "postgres://{}:{}@{}:{}/{}",
-        "azueljos", "azulejos-pg-pwd", "localhost", 5432, "azulejos"
-    );
-    let db_pool = connect_db(&uri).await;
-
+fn rocket() -> Rocket<Build> {
     rocket::build()
-        .manage(Box::new(default_module(db_pool).await.build()))
-        .attach(rocket::fairing::AdHoc::try_on_ignite(
-            "REST Adapter",
-            rest::configure_rest,
+        .attach(AdHoc::try_on_ignite(
+            "Application Module",
+            container::configure_module,
         ))
+        .attach(AdHoc::try_on_ignite("REST Adapter", rest::configure_rest))
+}
+
+/// Launch the REST server, or run the command given as first argument.
+#[rocket::main]
+async fn main() {
+    let command = env::args().nth(1);
+    match command.as_deref() {
+        None => {
+            if let Err(err) = rocket().launch().await {
+                log::error!("Unable to launch the REST server: {}", err);
+                process::exit(1);
+            }
+        }
+        Some("audit-ledger") => process::exit(cli::audit_ledger_command().await),
+        Some(command) => {
+            eprintln!("Unknown command '{}', expected 'audit-ledger'", command);
+            process::exit(2);
+        }
+    }
 }
@@ -1,20 +1,51 @@
-use super::{activity::ActivityBuilder, activity::ActivityWindow, money::Money};
+use derive_more::{Display, Error, From};
 
-#[derive(Debug, Builder)]
+use super::{
+    activity::ActivityWindow,
+    activity::{Activity, ActivityBuilder, ActivityId, TransferId},
+    customer::CustomerId,
+    money::{Currency, Money, MoneyError},
+};
+
+#[derive(Debug, Clone, Builder)]
 #[builder(pattern = "owned")]
 pub struct Account {
     #[builder(setter(strip_option))]
     id: Option<AccountId>,
+    /// Currency of every amount held by the account. Defaults to the baseline balance currency.
+    #[builder(default = "self.default_currency()?")]
+    currency: Currency,
     baseline_balance: Money,
     activity_window: ActivityWindow,
+    /// How far the balance may go below zero. No overdraft is allowed when unset.
+    #[builder(default)]
+    overdraft_limit: Option<Money>,
+    #[builder(default)]
+    status: AccountStatus,
+    /// Customer owning the account, if any.
+    #[builder(default)]
+    owner_id: Option<CustomerId>,
+}
+
+impl AccountBuilder {
+    fn default_currency(&self) -> Result<Currency, String> {
+        match self.baseline_balance {
+            Some(ref money) => Ok(money.currency()),
+            None => Err("Baseline balance is missing".into()),
+        }
+    }
 }
 
 impl Account {
     pub fn new(baseline_balance: Money, activity_window: ActivityWindow) -> Self {
         Self {
             id: None,
+            currency: baseline_balance.currency(),
             baseline_balance,
             activity_window,
+            overdraft_limit: None,
+            status: AccountStatus::Active,
+            owner_id: None,
         }
     }
 
@@ -25,8 +56,25 @@ impl Account {
     ) -> Self {
         Self {
             id: Some(id),
+            currency: baseline_balance.currency(),
             baseline_balance,
             activity_window,
+            overdraft_limit: None,
+            status: AccountStatus::Active,
+            owner_id: None,
+        }
+    }
+
+    /// Open a new, active account with a zero balance.
+    pub fn open(id: AccountId, currency: Currency, overdraft_limit: Option<Money>) -> Self {
+        Self {
+            id: Some(id),
+            currency,
+            baseline_balance: Money::zero(currency),
+            activity_window: ActivityWindow::new(vec![]),
+            overdraft_limit,
+            status: AccountStatus::Active,
+            owner_id: None,
         }
     }
 
@@ -35,56 +83,213 @@ impl Account {
         self.id.as_ref()
     }
 
-    pub fn calculate_balance(&self) -> Money {
-        let activity_balance = self.activity_window.calculate_balance(
-            self.id
-                .expect("Cannot calculate balance. Account Id is not set."),
-        );
+    /// Get the account's currency.
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
 
-        self.baseline_balance + activity_balance
+    /// Get the account's overdraft limit.
+    pub fn overdraft_limit(&self) -> Option<Money> {
+        self.overdraft_limit
     }
 
-    pub fn withdraw(&mut self, money: Money, target_id: AccountId) -> bool {
-        if !self.may_withdraw_money(money) {
-            return false;
+    /// Get the id of the customer owning the account.
+    pub fn owner_id(&self) -> Option<CustomerId> {
+        self.owner_id
+    }
+
+    /// Whether both accounts belong to the same customer.
+    pub fn has_same_owner(&self, other: &Account) -> bool {
+        self.owner_id.is_some() && self.owner_id == other.owner_id
+    }
+
+    /// Get the account's status.
+    pub fn status(&self) -> AccountStatus {
+        self.status
+    }
+
+    /// Freeze an active account, refusing withdrawals and deposits until it is unfrozen.
+    pub fn freeze(&mut self) -> Result<(), AccountError> {
+        self.transition(AccountStatus::Active, AccountStatus::Frozen)
+    }
+
+    /// Make a frozen account active again.
+    pub fn unfreeze(&mut self) -> Result<(), AccountError> {
+        self.transition(AccountStatus::Frozen, AccountStatus::Active)
+    }
+
+    /// Close an active or frozen account for good. Its balance must be zero.
+    pub fn close(&mut self) -> Result<(), AccountError> {
+        if self.status == AccountStatus::Closed {
+            return Err(AccountError::InvalidStatusTransition {
+                from: self.status,
+                to: AccountStatus::Closed,
+            });
         }
 
-        if let None = self.id {
-            return false;
+        let balance = self.calculate_balance()?;
+        if balance.amount() != 0 {
+            return Err(AccountError::NonZeroBalance(balance));
         }
 
-        let id = self.id.as_ref().unwrap().clone();
+        self.status = AccountStatus::Closed;
+        Ok(())
+    }
+
+    fn transition(&mut self, from: AccountStatus, to: AccountStatus) -> Result<(), AccountError> {
+        if self.status != from {
+            return Err(AccountError::InvalidStatusTransition {
+                from: self.status,
+                to,
+            });
+        }
+
+        self.status = to;
+        Ok(())
+    }
+
+    fn ensure_active(&self) -> Result<(), AccountError> {
+        match self.status {
+            AccountStatus::Active => Ok(()),
+            status => Err(AccountError::NotActive(status)),
+        }
+    }
+
+    /// Calculate the account balance, failing rather than overflowing.
+    pub fn calculate_balance(&self) -> Result<Money, AccountError> {
+        let id = self.id.ok_or(AccountError::MissingAccountId)?;
+        let activity_balance = self.activity_window.calculate_balance(id, self.currency)?;
+
+        Ok((self.baseline_balance + activity_balance)?)
+    }
+
+    pub fn withdraw(&mut self, money: Money, target_id: AccountId) -> Result<(), AccountError> {
+        self.withdraw_converted(money, None, target_id, None)
+    }
+
+    /// Withdraw `money` to be received as `converted_money` by a target account of another
+    /// currency, as the source half of `transfer_id`.
+    pub fn withdraw_converted(
+        &mut self,
+        money: Money,
+        converted_money: Option<Money>,
+        target_id: AccountId,
+        transfer_id: Option<TransferId>,
+    ) -> Result<(), AccountError> {
+        let id = self.id.ok_or(AccountError::MissingAccountId)?;
+        self.ensure_may_withdraw(money)?;
+
         let withdrawal = ActivityBuilder::default()
             .source_account_id(id)
             .target_account_id(target_id)
             .money(money)
+            .converted_money(converted_money)
+            .transfer_id(transfer_id)
             .build()
             .unwrap();
 
         self.activity_window.add_activity(withdrawal);
-        true
+        Ok(())
     }
 
-    fn may_withdraw_money(&self, money: Money) -> bool {
-        (self.calculate_balance() - money).is_positive_or_zero()
+    pub fn deposit(&mut self, money: Money, source_account: AccountId) -> Result<(), AccountError> {
+        self.deposit_converted(money, None, source_account, None)
     }
 
-    pub fn deposit(&mut self, money: Money, source_account: AccountId) -> bool {
-        if let None = self.id {
-            return false;
-        }
+    /// Deposit the `source_money` sent by a source account, received as `converted_money` when
+    /// the source account has another currency, as the target half of `transfer_id`.
+    pub fn deposit_converted(
+        &mut self,
+        source_money: Money,
+        converted_money: Option<Money>,
+        source_account: AccountId,
+        transfer_id: Option<TransferId>,
+    ) -> Result<(), AccountError> {
+        let id = self.id.ok_or(AccountError::MissingAccountId)?;
+        self.ensure_active()?;
+        self.ensure_currency(converted_money.unwrap_or(source_money))?;
 
-        let id = self.id.as_ref().unwrap().clone();
         let deposit = ActivityBuilder::default()
             .owner_account_id(id)
             .source_account_id(source_account)
             .target_account_id(id)
-            .money(money)
+            .money(source_money)
+            .converted_money(converted_money)
+            .transfer_id(transfer_id)
             .build()
             .unwrap();
 
         self.activity_window.add_activity(deposit);
-        true
+        Ok(())
+    }
+
+    /// Compensate `original`, a transfer this account took part in, by moving its money back
+    /// at the original rate, as a half of `transfer_id`: the target account withdraws what it
+    /// received and the source account gets back what it sent.
+    pub fn reverse(
+        &mut self,
+        original: &Activity,
+        transfer_id: TransferId,
+    ) -> Result<(), AccountError> {
+        let id = self.id.ok_or(AccountError::MissingAccountId)?;
+        let original_id = *original.id().ok_or(AccountError::MissingActivityId)?;
+        let money = *original.received_money();
+        let converted_money = original.converted_money().map(|_| *original.money());
+
+        let (source_id, target_id) = if *original.target_account_id() == id {
+            self.ensure_may_withdraw(money)?;
+            (id, *original.source_account_id())
+        } else if *original.source_account_id() == id {
+            self.ensure_active()?;
+            self.ensure_currency(*original.money())?;
+            (*original.target_account_id(), id)
+        } else {
+            return Err(AccountError::UnrelatedActivity(original_id));
+        };
+
+        let reversal = ActivityBuilder::default()
+            .owner_account_id(id)
+            .source_account_id(source_id)
+            .target_account_id(target_id)
+            .money(money)
+            .converted_money(converted_money)
+            .reverses_activity_id(Some(original_id))
+            .transfer_id(Some(transfer_id))
+            .build()
+            .unwrap();
+
+        self.activity_window.add_activity(reversal);
+        Ok(())
+    }
+
+    /// Fail unless `money` can be withdrawn from the account.
+    pub fn ensure_may_withdraw(&self, money: Money) -> Result<(), AccountError> {
+        self.ensure_active()?;
+        self.ensure_currency(money)?;
+
+        let available = self.calculate_available_funds()?;
+        if !(available - money)?.is_positive_or_zero() {
+            return Err(AccountError::InsufficientFunds {
+                available,
+                requested: money,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Calculate how much may be withdrawn: the balance plus the overdraft limit, if any.
+    pub fn calculate_available_funds(&self) -> Result<Money, AccountError> {
+        let balance = self.calculate_balance()?;
+
+        match self.overdraft_limit {
+            Some(overdraft_limit) => Ok((balance + overdraft_limit)?),
+            None => Ok(balance),
+        }
+    }
+
+    fn ensure_currency(&self, money: Money) -> Result<(), MoneyError> {
+        Money::zero(self.currency).ensure_same_currency(&money)
     }
 
     /// Get a reference to the account's baseline balance.
@@ -98,12 +303,80 @@ impl Account {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct AccountId(pub u64);
 
+/// Lifecycle stage of an account. Only active accounts accept withdrawals and deposits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Display)]
+pub enum AccountStatus {
+    #[default]
+    #[display(fmt = "active")]
+    Active,
+    #[display(fmt = "frozen")]
+    Frozen,
+    #[display(fmt = "closed")]
+    Closed,
+}
+
+impl AccountStatus {
+    pub const ALL: [AccountStatus; 3] = [
+        AccountStatus::Active,
+        AccountStatus::Frozen,
+        AccountStatus::Closed,
+    ];
+
+    /// Find the status persisted as `code`.
+    pub fn from_code(code: &str) -> Option<AccountStatus> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|status| status.code() == code)
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            AccountStatus::Active => "active",
+            AccountStatus::Frozen => "frozen",
+            AccountStatus::Closed => "closed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Display, Error, From)]
+pub enum AccountError {
+    #[display(fmt = "Account Id is not set")]
+    MissingAccountId,
+    #[display(fmt = "Activity Id is not set")]
+    MissingActivityId,
+    #[display(fmt = "Activity {} does not involve the account", "_0.0")]
+    #[from(ignore)]
+    UnrelatedActivity(#[error(not(source))] ActivityId),
+    #[display(
+        fmt = "Insufficient funds: {} available, {} requested",
+        available,
+        requested
+    )]
+    #[from(ignore)]
+    InsufficientFunds { available: Money, requested: Money },
+    #[display(fmt = "Account is {}", _0)]
+    #[from(ignore)]
+    NotActive(#[error(not(source))] AccountStatus),
+    #[display(fmt = "Account cannot go from {} to {}", from, to)]
+    #[from(ignore)]
+    InvalidStatusTransition {
+        from: AccountStatus,
+        to: AccountStatus,
+    },
+    #[display(fmt = "Account balance must be zero, but is {}", _0)]
+    #[from(ignore)]
+    NonZeroBalance(#[error(not(source))] Money),
+    #[display(fmt = "{}", _0)]
+    Money(MoneyError),
+}
+
 #[cfg(test)]
 pub mod tests {
-    use crate::domain::activity::tests::default_activity;
+    use crate::domain::{activity::tests::default_activity, money::tests::eur};
 
     use super::*;
 
@@ -113,16 +386,16 @@ pub mod tests {
         let account_id = AccountId(1);
         let account = default_account()
             .id(account_id)
-            .baseline_balance(Money(555))
+            .baseline_balance(eur(555))
             .activity_window(ActivityWindow::new(vec![
                 default_activity()
                     .target_account_id(account_id)
-                    .money(Money(999))
+                    .money(eur(999))
                     .build()
                     .unwrap(),
                 default_activity()
                     .target_account_id(account_id)
-                    .money(Money(1))
+                    .money(eur(1))
                     .build()
                     .unwrap(),
             ]))
@@ -133,7 +406,7 @@ pub mod tests {
         let balance = account.calculate_balance();
 
         // Expect
-        assert_eq!(balance, Money(1555));
+        assert_eq!(balance, Ok(eur(1555)));
     }
 
     #[test]
@@ -142,16 +415,16 @@ pub mod tests {
         let account_id = AccountId(1);
         let mut account = default_account()
             .id(account_id)
-            .baseline_balance(Money(555))
+            .baseline_balance(eur(555))
             .activity_window(ActivityWindow::new(vec![
                 default_activity()
                     .target_account_id(account_id)
-                    .money(Money(999))
+                    .money(eur(999))
                     .build()
                     .unwrap(),
                 default_activity()
                     .target_account_id(account_id)
-                    .money(Money(1))
+                    .money(eur(1))
                     .build()
                     .unwrap(),
             ]))
@@ -159,12 +432,12 @@ pub mod tests {
             .unwrap();
 
         // When
-        let success = account.withdraw(Money(555), AccountId(99));
+        let result = account.withdraw(eur(555), AccountId(99));
 
         // Expect
-        assert!(success);
+        assert!(result.is_ok());
         assert_eq!(3, account.activity_window().activities().len());
-        assert_eq!(Money(1000), account.calculate_balance());
+        assert_eq!(Ok(eur(1000)), account.calculate_balance());
     }
 
     #[test]
@@ -173,16 +446,16 @@ pub mod tests {
         let account_id = AccountId(1);
         let mut account = default_account()
             .id(account_id)
-            .baseline_balance(Money(555))
+            .baseline_balance(eur(555))
             .activity_window(ActivityWindow::new(vec![
                 default_activity()
                     .target_account_id(account_id)
-                    .money(Money(999))
+                    .money(eur(999))
                     .build()
                     .unwrap(),
                 default_activity()
                     .target_account_id(account_id)
-                    .money(Money(1))
+                    .money(eur(1))
                     .build()
                     .unwrap(),
             ]))
@@ -190,12 +463,18 @@ pub mod tests {
             .unwrap();
 
         // When
-        let success = account.withdraw(Money(1556), AccountId(99));
+        let result = account.withdraw(eur(1556), AccountId(99));
 
         // Expect
-        assert!(!success);
+        assert_eq!(
+            result,
+            Err(AccountError::InsufficientFunds {
+                available: eur(1555),
+                requested: eur(1556),
+            })
+        );
         assert_eq!(2, account.activity_window().activities().len());
-        assert_eq!(Money(1555), account.calculate_balance());
+        assert_eq!(Ok(eur(1555)), account.calculate_balance());
     }
 
     #[test]
@@ -204,16 +483,16 @@ pub mod tests {
         let account_id = AccountId(1);
         let mut account = default_account()
             .id(account_id)
-            .baseline_balance(Money(555))
+            .baseline_balance(eur(555))
             .activity_window(ActivityWindow::new(vec![
                 default_activity()
                     .target_account_id(account_id)
-                    .money(Money(999))
+                    .money(eur(999))
                     .build()
                     .unwrap(),
                 default_activity()
                     .target_account_id(account_id)
-                    .money(Money(1))
+                    .money(eur(1))
                     .build()
                     .unwrap(),
             ]))
@@ -221,18 +500,338 @@ pub mod tests {
             .unwrap();
 
         // When
-        let success = account.deposit(Money(445), AccountId(99));
+        let result = account.deposit(eur(445), AccountId(99));
 
         // Expect
-        assert!(success);
+        assert!(result.is_ok());
         assert_eq!(3, account.activity_window().activities().len());
-        assert_eq!(Money(2000), account.calculate_balance());
+        assert_eq!(Ok(eur(2000)), account.calculate_balance());
+    }
+
+    #[test]
+    fn deposit_failure() {
+        // Given
+        let mut account = Account::new(eur(555), ActivityWindow::new(vec![]));
+
+        // When
+        let result = account.deposit(eur(445), AccountId(99));
+
+        // Expect
+        assert_eq!(result, Err(AccountError::MissingAccountId));
+        assert!(account.activity_window().activities().is_empty());
+    }
+
+    #[test]
+    fn withdrawal_rejects_other_currencies() {
+        // Given
+        let mut account = default_account().build().unwrap();
+
+        // When
+        let result = account.withdraw(Money::new(1, Currency::USD), AccountId(99));
+
+        // Expect
+        assert_eq!(
+            result,
+            Err(AccountError::Money(MoneyError::CurrencyMismatch {
+                expected: Currency::EUR,
+                actual: Currency::USD,
+            }))
+        );
+        assert_eq!(2, account.activity_window().activities().len());
+    }
+
+    #[test]
+    fn calculate_balance_reports_overflow() {
+        // Given
+        let account = default_account()
+            .id(AccountId(41))
+            .baseline_balance(eur(i64::MAX))
+            .build()
+            .unwrap();
+
+        // Expect
+        assert_eq!(
+            account.calculate_balance(),
+            Err(AccountError::Money(MoneyError::Overflow(Currency::EUR)))
+        );
+    }
+
+    #[test]
+    fn converted_deposit_success() {
+        // Given
+        let account_id = AccountId(1);
+        let mut account = default_account()
+            .id(account_id)
+            .baseline_balance(Money::new(0, Currency::USD))
+            .activity_window(ActivityWindow::new(vec![]))
+            .build()
+            .unwrap();
+
+        // When
+        let result = account.deposit_converted(
+            eur(1000),
+            Some(Money::new(1085, Currency::USD)),
+            AccountId(99),
+            Some(TransferId(3)),
+        );
+
+        // Expect
+        assert!(result.is_ok());
+        let deposit = &account.activity_window().activities()[0];
+        assert_eq!(*deposit.money(), eur(1000));
+        assert_eq!(deposit.transfer_id(), Some(&TransferId(3)));
+        assert_eq!(
+            deposit.converted_money(),
+            Some(&Money::new(1085, Currency::USD))
+        );
+        assert_eq!(
+            account.calculate_balance(),
+            Ok(Money::new(1085, Currency::USD))
+        );
+    }
+
+    #[test]
+    fn reversal_moves_money_back_at_the_original_rate() {
+        // Given
+        let original = default_activity()
+            .id(Some(ActivityId(7)))
+            .source_account_id(AccountId(1))
+            .target_account_id(AccountId(2))
+            .money(eur(1000))
+            .converted_money(Some(Money::new(1085, Currency::USD)))
+            .build()
+            .unwrap();
+        let mut source = default_account()
+            .id(AccountId(1))
+            .activity_window(ActivityWindow::new(vec![]))
+            .build()
+            .unwrap();
+        let mut target = default_account()
+            .id(AccountId(2))
+            .baseline_balance(Money::new(1085, Currency::USD))
+            .activity_window(ActivityWindow::new(vec![]))
+            .build()
+            .unwrap();
+
+        // When
+        target.reverse(&original, TransferId(8)).unwrap();
+        source.reverse(&original, TransferId(8)).unwrap();
+
+        // Expect
+        assert_eq!(target.calculate_balance(), Ok(Money::new(0, Currency::USD)));
+        assert_eq!(source.calculate_balance(), Ok(eur(1999)));
+        let withdrawal = &target.activity_window().activities()[0];
+        assert_eq!(*withdrawal.source_account_id(), AccountId(2));
+        assert_eq!(*withdrawal.target_account_id(), AccountId(1));
+        assert_eq!(withdrawal.reverses_activity_id(), Some(&ActivityId(7)));
+        assert_eq!(withdrawal.transfer_id(), Some(&TransferId(8)));
+        let deposit = &source.activity_window().activities()[0];
+        assert_eq!(*deposit.owner_account_id(), AccountId(1));
+        assert_eq!(deposit.reverses_activity_id(), Some(&ActivityId(7)));
+    }
+
+    #[test]
+    fn reversal_requires_funds_and_involvement() {
+        // Given
+        let original = default_activity()
+            .id(Some(ActivityId(7)))
+            .source_account_id(AccountId(1))
+            .target_account_id(AccountId(2))
+            .money(eur(1000))
+            .build()
+            .unwrap();
+        let mut target = default_account()
+            .id(AccountId(2))
+            .baseline_balance(eur(999))
+            .activity_window(ActivityWindow::new(vec![]))
+            .build()
+            .unwrap();
+        let mut other = default_account().id(AccountId(3)).build().unwrap();
+
+        // Expect
+        assert!(matches!(
+            target.reverse(&original, TransferId(8)),
+            Err(AccountError::InsufficientFunds { .. })
+        ));
+        assert_eq!(
+            other.reverse(&original, TransferId(8)),
+            Err(AccountError::UnrelatedActivity(ActivityId(7)))
+        );
+    }
+
+    #[test]
+    fn builder_defaults_currency_to_baseline_balance_currency() {
+        // When
+        let account = default_account()
+            .baseline_balance(Money::new(0, Currency::JPY))
+            .activity_window(ActivityWindow::new(vec![]))
+            .build()
+            .unwrap();
+
+        // Expect
+        assert_eq!(account.currency(), Currency::JPY);
+    }
+
+    #[test]
+    fn withdrawal_within_overdraft_limit_succeeds() {
+        // Given
+        let mut account = default_account()
+            .baseline_balance(eur(1000))
+            .activity_window(ActivityWindow::new(vec![]))
+            .overdraft_limit(Some(eur(500)))
+            .build()
+            .unwrap();
+
+        // When
+        let result = account.withdraw(eur(1500), AccountId(99));
+
+        // Expect
+        assert!(result.is_ok());
+        assert_eq!(account.calculate_balance(), Ok(eur(-500)));
+        assert_eq!(account.calculate_available_funds(), Ok(eur(0)));
+    }
+
+    #[test]
+    fn withdrawal_beyond_overdraft_limit_fails() {
+        // Given
+        let mut account = default_account()
+            .baseline_balance(eur(1000))
+            .activity_window(ActivityWindow::new(vec![]))
+            .overdraft_limit(Some(eur(500)))
+            .build()
+            .unwrap();
+
+        // When
+        let result = account.withdraw(eur(1500), AccountId(99));
+        let result = result.and_then(|_| account.withdraw(eur(1), AccountId(99)));
+
+        // Expect
+        assert_eq!(
+            result,
+            Err(AccountError::InsufficientFunds {
+                available: eur(0),
+                requested: eur(1),
+            })
+        );
+    }
+
+    #[test]
+    fn frozen_account_refuses_withdrawals_and_deposits() {
+        // Given
+        let mut account = default_account().build().unwrap();
+
+        // When
+        account.freeze().unwrap();
+
+        // Expect
+        assert_eq!(account.status(), AccountStatus::Frozen);
+        assert_eq!(
+            account.withdraw(eur(1), AccountId(99)),
+            Err(AccountError::NotActive(AccountStatus::Frozen))
+        );
+        assert_eq!(
+            account.deposit(eur(1), AccountId(99)),
+            Err(AccountError::NotActive(AccountStatus::Frozen))
+        );
+
+        // When
+        account.unfreeze().unwrap();
+
+        // Expect
+        assert!(account.deposit(eur(1), AccountId(99)).is_ok());
+    }
+
+    #[test]
+    fn only_active_accounts_can_be_frozen() {
+        // Given
+        let mut account = default_account()
+            .status(AccountStatus::Closed)
+            .build()
+            .unwrap();
+
+        // Expect
+        assert_eq!(
+            account.freeze(),
+            Err(AccountError::InvalidStatusTransition {
+                from: AccountStatus::Closed,
+                to: AccountStatus::Frozen,
+            })
+        );
+        assert_eq!(
+            account.unfreeze(),
+            Err(AccountError::InvalidStatusTransition {
+                from: AccountStatus::Closed,
+                to: AccountStatus::Active,
+            })
+        );
+    }
+
+    #[test]
+    fn close_requires_zero_balance() {
+        // Given
+        let mut account = default_account()
+            .baseline_balance(eur(10))
+            .activity_window(ActivityWindow::new(vec![]))
+            .build()
+            .unwrap();
+
+        // When
+        let result = account.close();
+
+        // Expect
+        assert_eq!(result, Err(AccountError::NonZeroBalance(eur(10))));
+        assert_eq!(account.status(), AccountStatus::Active);
+
+        // When
+        account.withdraw(eur(10), AccountId(99)).unwrap();
+        account.freeze().unwrap();
+        let result = account.close();
+
+        // Expect
+        assert!(result.is_ok());
+        assert_eq!(account.status(), AccountStatus::Closed);
+        assert!(matches!(
+            account.close(),
+            Err(AccountError::InvalidStatusTransition { .. })
+        ));
+    }
+
+    #[test]
+    fn accounts_without_owner_never_have_the_same_owner() {
+        // Given
+        let owned = default_account()
+            .owner_id(Some(CustomerId(1)))
+            .build()
+            .unwrap();
+        let also_owned = default_account()
+            .owner_id(Some(CustomerId(1)))
+            .build()
+            .unwrap();
+        let other = default_account()
+            .owner_id(Some(CustomerId(2)))
+            .build()
+            .unwrap();
+        let unowned = default_account().build().unwrap();
+
+        // Expect
+        assert!(owned.has_same_owner(&also_owned));
+        assert!(!owned.has_same_owner(&other));
+        assert!(!owned.has_same_owner(&unowned));
+        assert!(!unowned.has_same_owner(&unowned));
+    }
+
+    #[test]
+    fn status_codes_round_trip() {
+        for status in AccountStatus::ALL {
+            assert_eq!(AccountStatus::from_code(status.code()), Some(status));
+        }
+        assert_eq!(AccountStatus::from_code("open"), None);
     }
 
     pub fn default_account() -> AccountBuilder {
         AccountBuilder::default()
             .id(AccountId(42))
-            .baseline_balance(Money(999))
+            .baseline_balance(eur(999))
             .activity_window(ActivityWindow::new(vec![
                 default_activity().build().unwrap(),
                 default_activity().build().unwrap(),
@@ -1,8 +1,13 @@
+use std::iter;
+
 use chrono::{DateTime, Utc};
 
-use super::{account::AccountId, money::Money};
+use super::{
+    account::AccountId,
+    money::{Currency, Money, MoneyError},
+};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ActivityWindow {
     activities: Vec<Activity>,
 }
@@ -43,20 +48,33 @@ impl ActivityWindow {
             .clone()
     }
 
-    pub fn calculate_balance(&self, id: AccountId) -> Money {
-        let deposit_balance = self
-            .activities
-            .iter()
-            .filter(|a| a.target_account_id() == &id)
-            .map(Activity::money)
-            .fold(Money(0), |acc, m| acc + *m);
-
-        let withdrawal_balance = self
-            .activities
-            .iter()
-            .filter(|a| a.source_account_id() == &id)
-            .map(Activity::money)
-            .fold(Money(0), |acc, m| acc + *m);
+    /// Calculate the balance of account `id` over the window, in `currency`.
+    ///
+    /// Fails if any activity of the account is in another currency, or on overflow.
+    pub fn calculate_balance(
+        &self,
+        id: AccountId,
+        currency: Currency,
+    ) -> Result<Money, MoneyError> {
+        let zero = Money::zero(currency);
+
+        let deposit_balance: Money = iter::once(&zero)
+            .chain(
+                self.activities
+                    .iter()
+                    .filter(|a| a.target_account_id() == &id)
+                    .map(Activity::received_money),
+            )
+            .sum::<Result<_, _>>()?;
+
+        let withdrawal_balance: Money = iter::once(&zero)
+            .chain(
+                self.activities
+                    .iter()
+                    .filter(|a| a.source_account_id() == &id)
+                    .map(Activity::money),
+            )
+            .sum::<Result<_, _>>()?;
 
         deposit_balance - withdrawal_balance
     }
@@ -72,7 +90,17 @@ pub struct Activity {
     target_account_id: AccountId,
     #[builder(default = "Utc::now()")]
     timestamp: DateTime<Utc>,
+    /// Amount sent by the source account, in its currency.
     money: Money,
+    /// Amount received by the target account, when converted into another currency.
+    #[builder(default = "None")]
+    converted_money: Option<Money>,
+    /// Activity compensated by this one, when it reverses a transfer.
+    #[builder(default = "None")]
+    reverses_activity_id: Option<ActivityId>,
+    /// Transfer recorded by this activity and its mirror, owned by the other account.
+    #[builder(default = "None")]
+    transfer_id: Option<TransferId>,
 }
 
 impl ActivityBuilder {
@@ -99,6 +127,9 @@ impl Activity {
             target_account_id,
             timestamp,
             money,
+            converted_money: None,
+            reverses_activity_id: None,
+            transfer_id: None,
         }
     }
 
@@ -117,6 +148,9 @@ impl Activity {
             target_account_id,
             timestamp,
             money,
+            converted_money: None,
+            reverses_activity_id: None,
+            transfer_id: None,
         }
     }
 
@@ -144,41 +178,61 @@ impl Activity {
         &self.timestamp
     }
 
-    /// Get a reference to the activity's money.
+    /// Get a reference to the activity's money, sent by the source account.
     pub fn money(&self) -> &Money {
         &self.money
     }
 
+    /// Get a reference to the activity's converted money, if any.
+    pub fn converted_money(&self) -> Option<&Money> {
+        self.converted_money.as_ref()
+    }
+
+    /// Get a reference to the money received by the target account.
+    pub fn received_money(&self) -> &Money {
+        self.converted_money.as_ref().unwrap_or(&self.money)
+    }
+
+    /// Get a reference to the id of the activity reversed by this one, if any.
+    pub fn reverses_activity_id(&self) -> Option<&ActivityId> {
+        self.reverses_activity_id.as_ref()
+    }
+
+    /// Get a reference to the id of the transfer recorded by the activity, if any.
+    pub fn transfer_id(&self) -> Option<&TransferId> {
+        self.transfer_id.as_ref()
+    }
+
+    pub fn with_id(self, id: ActivityId) -> Activity {
+        Activity {
+            id: Some(id),
+            ..self
+        }
+    }
+
     pub fn with_timestamp(self, ts: DateTime<Utc>) -> Activity {
-        match self.id {
-            Some(id) => Activity::new_with_id(
-                id,
-                self.owner_account_id,
-                self.source_account_id,
-                self.target_account_id,
-                ts,
-                self.money,
-            ),
-            None => Activity::new(
-                self.owner_account_id,
-                self.source_account_id,
-                self.target_account_id,
-                ts,
-                self.money,
-            ),
+        Activity {
+            timestamp: ts,
+            ..self
         }
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ActivityId(pub u64);
 
+/// Identifies a transfer, shared by the withdrawal and the deposit recording it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TransferId(pub u64);
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
 
     use chrono::prelude::*;
 
+    use crate::domain::money::tests::eur;
+
     #[test]
     fn activity_window_calculates_start_timestamp() {
         // Given
@@ -220,33 +274,107 @@ pub mod tests {
             ActivityBuilder::default()
                 .source_account_id(account1)
                 .target_account_id(account2)
-                .money(Money(999))
+                .money(eur(999))
                 .build()
                 .unwrap(),
             ActivityBuilder::default()
                 .source_account_id(account1)
                 .target_account_id(account2)
-                .money(Money(1))
+                .money(eur(1))
                 .build()
                 .unwrap(),
             ActivityBuilder::default()
                 .source_account_id(account2)
                 .target_account_id(account1)
-                .money(Money(500))
+                .money(eur(500))
+                .build()
+                .unwrap(),
+        ]);
+
+        // Expect
+        assert_eq!(
+            window.calculate_balance(account1, Currency::EUR),
+            Ok(eur(-500))
+        );
+        assert_eq!(
+            window.calculate_balance(account2, Currency::EUR),
+            Ok(eur(500))
+        );
+    }
+
+    #[test]
+    fn calculates_balance_of_converted_activities() {
+        // Given
+        let account1 = AccountId(1);
+        let account2 = AccountId(2);
+        let window = ActivityWindow::new(vec![ActivityBuilder::default()
+            .source_account_id(account1)
+            .target_account_id(account2)
+            .money(eur(1000))
+            .converted_money(Some(Money::new(1085, Currency::USD)))
+            .build()
+            .unwrap()]);
+
+        // Expect
+        assert_eq!(
+            window.calculate_balance(account1, Currency::EUR),
+            Ok(eur(-1000))
+        );
+        assert_eq!(
+            window.calculate_balance(account2, Currency::USD),
+            Ok(Money::new(1085, Currency::USD))
+        );
+    }
+
+    #[test]
+    fn calculate_balance_rejects_other_currencies() {
+        // Given
+        let account1 = AccountId(1);
+        let window = ActivityWindow::new(vec![default_activity()
+            .target_account_id(account1)
+            .money(Money::new(999, Currency::USD))
+            .build()
+            .unwrap()]);
+
+        // Expect
+        assert_eq!(
+            window.calculate_balance(account1, Currency::EUR),
+            Err(MoneyError::CurrencyMismatch {
+                expected: Currency::EUR,
+                actual: Currency::USD,
+            })
+        );
+    }
+
+    #[test]
+    fn calculate_balance_reports_overflow() {
+        // Given
+        let account1 = AccountId(1);
+        let window = ActivityWindow::new(vec![
+            default_activity()
+                .target_account_id(account1)
+                .money(eur(i64::MAX))
+                .build()
+                .unwrap(),
+            default_activity()
+                .target_account_id(account1)
+                .money(eur(1))
                 .build()
                 .unwrap(),
         ]);
 
         // Expect
-        assert_eq!(window.calculate_balance(account1), Money(-500));
-        assert_eq!(window.calculate_balance(account2), Money(500));
+        assert_eq!(
+            window.calculate_balance(account1, Currency::EUR),
+            Err(MoneyError::Overflow(Currency::EUR))
+        );
     }
 
     pub fn default_activity() -> ActivityBuilder {
         ActivityBuilder::default()
             .source_account_id(AccountId(42))
             .target_account_id(AccountId(41))
-            .money(Money(999))
+            .money(eur(999))
             .clone()
     }
 
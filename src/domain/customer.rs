@@ -0,0 +1,42 @@
+use super::account::AccountId;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CustomerId(pub u64);
+
+/// Customer owning any number of accounts.
+#[derive(Debug, Clone)]
+pub struct Customer {
+    id: CustomerId,
+    name: String,
+    account_ids: Vec<AccountId>,
+}
+
+impl Customer {
+    pub fn new(id: CustomerId, name: String, account_ids: Vec<AccountId>) -> Self {
+        Self {
+            id,
+            name,
+            account_ids,
+        }
+    }
+
+    /// Get a reference to the customer's id.
+    pub fn id(&self) -> &CustomerId {
+        &self.id
+    }
+
+    /// Get a reference to the customer's name.
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Get a reference to the ids of the customer's accounts.
+    pub fn account_ids(&self) -> &[AccountId] {
+        self.account_ids.as_slice()
+    }
+
+    /// Whether account `account_id` belongs to the customer.
+    pub fn owns(&self, account_id: AccountId) -> bool {
+        self.account_ids.contains(&account_id)
+    }
+}
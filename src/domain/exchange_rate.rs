@@ -0,0 +1,170 @@
+use std::convert::TryFrom;
+
+use derive_more::{Display, Error};
+
+use super::money::{parse_fixed_point, Currency, Money, MoneyError};
+
+/// Rate at which `source` amounts are converted into `target` amounts.
+///
+/// The rate is a fixed-point number of `ExchangeRate::SCALE` decimal digits, so conversions are
+/// exact up to the rounding of the converted amount to the target minor unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExchangeRate {
+    source: Currency,
+    target: Currency,
+    rate: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Display, Error)]
+#[display(fmt = "Invalid exchange rate '{}': expected a positive decimal", _0)]
+pub struct InvalidExchangeRate(#[error(not(source))] String);
+
+impl ExchangeRate {
+    /// Number of decimal digits of rates.
+    pub const SCALE: u8 = 8;
+
+    /// Build a rate from its fixed-point value, e.g. `108_500_000` for `1.085`.
+    pub fn new(source: Currency, target: Currency, rate: i64) -> Result<Self, InvalidExchangeRate> {
+        if rate <= 0 {
+            return Err(InvalidExchangeRate(rate.to_string()));
+        }
+
+        Ok(Self {
+            source,
+            target,
+            rate,
+        })
+    }
+
+    /// Build a rate from its decimal representation, e.g. `1.085`.
+    pub fn from_decimal_str(
+        source: Currency,
+        target: Currency,
+        rate: &str,
+    ) -> Result<Self, InvalidExchangeRate> {
+        let invalid = || InvalidExchangeRate(rate.trim().to_owned());
+        let rate = parse_fixed_point(rate, Self::SCALE).map_err(|_| invalid())?;
+
+        Self::new(source, target, rate).map_err(|_| invalid())
+    }
+
+    /// Rate converting `currency` amounts into themselves.
+    pub fn identity(currency: Currency) -> Self {
+        Self {
+            source: currency,
+            target: currency,
+            rate: 10i64.pow(Self::SCALE as u32),
+        }
+    }
+
+    pub fn source(&self) -> Currency {
+        self.source
+    }
+
+    pub fn target(&self) -> Currency {
+        self.target
+    }
+
+    /// Get the fixed-point value of the rate.
+    pub fn rate(&self) -> i64 {
+        self.rate
+    }
+
+    /// Convert `money` into the target currency, rounding half away from zero to the target
+    /// minor unit.
+    pub fn convert(&self, money: Money) -> Result<Money, MoneyError> {
+        Money::zero(self.source).ensure_same_currency(&money)?;
+
+        let numerator = i128::from(money.amount())
+            * i128::from(self.rate)
+            * 10i128.pow(self.target.scale() as u32);
+        let denominator = 10i128.pow(Self::SCALE as u32) * 10i128.pow(self.source.scale() as u32);
+
+        let mut amount = numerator / denominator;
+        if 2 * (numerator % denominator).abs() >= denominator {
+            amount += numerator.signum();
+        }
+
+        i64::try_from(amount)
+            .map(|amount| Money::new(amount, self.target))
+            .map_err(|_| MoneyError::Overflow(self.target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::money::tests::eur;
+
+    use super::*;
+
+    #[test]
+    fn it_parses_rates() {
+        // When
+        let rate = ExchangeRate::from_decimal_str(Currency::EUR, Currency::USD, "1.085").unwrap();
+
+        // Expect
+        assert_eq!(rate.rate(), 108_500_000);
+        assert!(ExchangeRate::from_decimal_str(Currency::EUR, Currency::USD, "0").is_err());
+        assert!(ExchangeRate::from_decimal_str(Currency::EUR, Currency::USD, "-1").is_err());
+        assert!(
+            ExchangeRate::from_decimal_str(Currency::EUR, Currency::USD, "1.0000000001").is_err()
+        );
+        assert!(ExchangeRate::from_decimal_str(Currency::EUR, Currency::USD, "abc").is_err());
+    }
+
+    #[test]
+    fn it_converts_money() {
+        // Given
+        let eur_usd =
+            ExchangeRate::from_decimal_str(Currency::EUR, Currency::USD, "1.085").unwrap();
+        let eur_jpy =
+            ExchangeRate::from_decimal_str(Currency::EUR, Currency::JPY, "130.5").unwrap();
+
+        // Expect
+        assert_eq!(
+            eur_usd.convert(eur(10_000)),
+            Ok(Money::new(10_850, Currency::USD))
+        );
+        // 0.05 EUR = 0.05425 USD, rounded to 0.05 USD
+        assert_eq!(eur_usd.convert(eur(5)), Ok(Money::new(5, Currency::USD)));
+        // 0.07 EUR = 0.07595 USD, rounded to 0.08 USD
+        assert_eq!(eur_usd.convert(eur(7)), Ok(Money::new(8, Currency::USD)));
+        assert_eq!(eur_usd.convert(eur(-7)), Ok(Money::new(-8, Currency::USD)));
+        // 12.34 EUR = 1610.37 JPY, rounded to 1610 JPY
+        assert_eq!(
+            eur_jpy.convert(eur(1234)),
+            Ok(Money::new(1610, Currency::JPY))
+        );
+    }
+
+    #[test]
+    fn it_rejects_other_currencies() {
+        // Given
+        let rate = ExchangeRate::identity(Currency::USD);
+
+        // Expect
+        assert_eq!(
+            rate.convert(eur(1)),
+            Err(MoneyError::CurrencyMismatch {
+                expected: Currency::USD,
+                actual: Currency::EUR,
+            })
+        );
+        assert_eq!(
+            rate.convert(Money::new(1, Currency::USD)),
+            Ok(Money::new(1, Currency::USD))
+        );
+    }
+
+    #[test]
+    fn it_reports_overflow() {
+        // Given
+        let rate = ExchangeRate::from_decimal_str(Currency::EUR, Currency::JPY, "130.5").unwrap();
+
+        // Expect
+        assert_eq!(
+            rate.convert(eur(i64::MAX)),
+            Err(MoneyError::Overflow(Currency::JPY))
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use std::{cmp::Ordering, fmt::Debug};
+
+use super::{
+    account::AccountId,
+    money::{Money, MoneyError},
+};
+
+/// Transfer a fee is charged for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeContext {
+    source_account_id: AccountId,
+    target_account_id: AccountId,
+    same_owner: bool,
+}
+
+impl FeeContext {
+    pub fn new(source_account_id: AccountId, target_account_id: AccountId) -> Self {
+        Self {
+            source_account_id,
+            target_account_id,
+            same_owner: false,
+        }
+    }
+
+    /// Set whether both accounts belong to the same owner.
+    pub fn with_same_owner(self, same_owner: bool) -> Self {
+        Self { same_owner, ..self }
+    }
+
+    pub fn source_account_id(&self) -> AccountId {
+        self.source_account_id
+    }
+
+    pub fn target_account_id(&self) -> AccountId {
+        self.target_account_id
+    }
+
+    pub fn same_owner(&self) -> bool {
+        self.same_owner
+    }
+}
+
+/// Rule computing the fee charged to the source account of a transfer, on top of the
+/// transferred money.
+pub trait FeePolicy: Debug + Send + Sync {
+    /// Compute the fee of transferring `money`, in the currency of `money`.
+    fn fee(&self, money: Money, context: &FeeContext) -> Result<Money, MoneyError>;
+}
+
+/// Transfers are free.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoFee;
+
+impl FeePolicy for NoFee {
+    fn fee(&self, money: Money, _context: &FeeContext) -> Result<Money, MoneyError> {
+        Ok(Money::zero(money.currency()))
+    }
+}
+
+/// Same fee for every transfer, which must be of the fee currency.
+#[derive(Debug, Clone, Copy)]
+pub struct FlatFee {
+    fee: Money,
+}
+
+impl FlatFee {
+    pub fn new(fee: Money) -> Self {
+        Self { fee }
+    }
+}
+
+impl FeePolicy for FlatFee {
+    fn fee(&self, money: Money, _context: &FeeContext) -> Result<Money, MoneyError> {
+        self.fee.ensure_same_currency(&money)?;
+        Ok(self.fee)
+    }
+}
+
+/// Fee proportional to the transferred money, rounded half up to the minor unit.
+#[derive(Debug, Clone, Copy)]
+pub struct PercentageFee {
+    /// Fee rate, in hundredths of a percent.
+    basis_points: u32,
+}
+
+impl PercentageFee {
+    pub fn new(basis_points: u32) -> Self {
+        Self { basis_points }
+    }
+}
+
+impl FeePolicy for PercentageFee {
+    fn fee(&self, money: Money, _context: &FeeContext) -> Result<Money, MoneyError> {
+        // Cannot overflow: |i64| * u32 fits in i128
+        let fee = (i128::from(money.amount()) * i128::from(self.basis_points) + 5_000) / 10_000;
+
+        Ok(Money::new(fee as i64, money.currency()))
+    }
+}
+
+/// Upper bound, inclusive, of the transferred money a policy of a `TieredFee` applies to.
+#[derive(Debug)]
+pub struct FeeTier {
+    up_to: Money,
+    policy: Box<dyn FeePolicy>,
+}
+
+impl FeeTier {
+    pub fn new(up_to: Money, policy: Box<dyn FeePolicy>) -> Self {
+        Self { up_to, policy }
+    }
+}
+
+/// Fee depending on the transferred money: the policy of the first tier whose bound is not
+/// exceeded applies, or `otherwise` when every bound is exceeded.
+#[derive(Debug)]
+pub struct TieredFee {
+    tiers: Vec<FeeTier>,
+    otherwise: Box<dyn FeePolicy>,
+}
+
+impl TieredFee {
+    pub fn new(tiers: Vec<FeeTier>, otherwise: Box<dyn FeePolicy>) -> Self {
+        Self { tiers, otherwise }
+    }
+}
+
+impl FeePolicy for TieredFee {
+    fn fee(&self, money: Money, context: &FeeContext) -> Result<Money, MoneyError> {
+        for tier in &self.tiers {
+            tier.up_to.ensure_same_currency(&money)?;
+            if money.partial_cmp(&tier.up_to) != Some(Ordering::Greater) {
+                return tier.policy.fee(money, context);
+            }
+        }
+
+        self.otherwise.fee(money, context)
+    }
+}
+
+/// Transfers between accounts of the same owner are free, other transfers are charged
+/// according to `policy`.
+#[derive(Debug)]
+pub struct FreeWithinSameOwner {
+    policy: Box<dyn FeePolicy>,
+}
+
+impl FreeWithinSameOwner {
+    pub fn new(policy: Box<dyn FeePolicy>) -> Self {
+        Self { policy }
+    }
+}
+
+impl FeePolicy for FreeWithinSameOwner {
+    fn fee(&self, money: Money, context: &FeeContext) -> Result<Money, MoneyError> {
+        if context.same_owner() {
+            return NoFee.fee(money, context);
+        }
+
+        self.policy.fee(money, context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::money::{tests::eur, Currency};
+
+    use super::*;
+
+    fn context() -> FeeContext {
+        FeeContext::new(AccountId(1), AccountId(2))
+    }
+
+    #[test]
+    fn no_fee_is_free() {
+        assert_eq!(NoFee.fee(eur(1000), &context()), Ok(eur(0)));
+    }
+
+    #[test]
+    fn flat_fee_is_constant() {
+        // Given
+        let policy = FlatFee::new(eur(50));
+
+        // Expect
+        assert_eq!(policy.fee(eur(1), &context()), Ok(eur(50)));
+        assert_eq!(policy.fee(eur(1_000_000), &context()), Ok(eur(50)));
+        assert!(policy
+            .fee(Money::new(1, Currency::USD), &context())
+            .is_err());
+    }
+
+    #[test]
+    fn percentage_fee_is_proportional() {
+        // Given
+        let policy = PercentageFee::new(150);
+
+        // Expect
+        assert_eq!(policy.fee(eur(10_000), &context()), Ok(eur(150)));
+        // 1.5% of 0.33 EUR is 0.00495 EUR, rounded to 0.00 EUR
+        assert_eq!(policy.fee(eur(33), &context()), Ok(eur(0)));
+        // 1.5% of 0.34 EUR is 0.0051 EUR, rounded to 0.01 EUR
+        assert_eq!(policy.fee(eur(34), &context()), Ok(eur(1)));
+    }
+
+    #[test]
+    fn tiered_fee_applies_first_matching_tier() {
+        // Given
+        let policy = TieredFee::new(
+            vec![
+                FeeTier::new(eur(1000), Box::new(NoFee)),
+                FeeTier::new(eur(100_000), Box::new(FlatFee::new(eur(50)))),
+            ],
+            Box::new(PercentageFee::new(10)),
+        );
+
+        // Expect
+        assert_eq!(policy.fee(eur(1000), &context()), Ok(eur(0)));
+        assert_eq!(policy.fee(eur(1001), &context()), Ok(eur(50)));
+        assert_eq!(policy.fee(eur(100_000), &context()), Ok(eur(50)));
+        assert_eq!(policy.fee(eur(1_000_000), &context()), Ok(eur(1000)));
+    }
+
+    #[test]
+    fn free_within_same_owner() {
+        // Given
+        let policy = FreeWithinSameOwner::new(Box::new(FlatFee::new(eur(50))));
+
+        // Expect
+        assert_eq!(policy.fee(eur(1000), &context()), Ok(eur(50)));
+        assert_eq!(
+            policy.fee(eur(1000), &context().with_same_owner(true)),
+            Ok(eur(0))
+        );
+    }
+}
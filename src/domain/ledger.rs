@@ -0,0 +1,330 @@
+use std::collections::{HashMap, VecDeque};
+
+use super::{
+    account::AccountId,
+    activity::{Activity, ActivityId, TransferId},
+    money::{Currency, Money, MoneyError},
+};
+
+/// Both halves of a single transfer, recorded with different amounts.
+#[derive(Debug, Clone)]
+pub struct LedgerMismatch {
+    withdrawal: Activity,
+    deposit: Activity,
+}
+
+impl LedgerMismatch {
+    /// Get a reference to the half owned by the source account.
+    pub fn withdrawal(&self) -> &Activity {
+        &self.withdrawal
+    }
+
+    /// Get a reference to the half owned by the target account.
+    pub fn deposit(&self) -> &Activity {
+        &self.deposit
+    }
+}
+
+/// Consistency report of the double-entry ledger, where every transfer is recorded twice: once
+/// owned by its source account, and once owned by its target account.
+#[derive(Debug, Clone)]
+pub struct LedgerAudit {
+    activity_count: usize,
+    pair_count: usize,
+    orphans: Vec<Activity>,
+    mismatches: Vec<LedgerMismatch>,
+    imbalances: Vec<Money>,
+}
+
+/// Amounts and references that must be equal for two activities to mirror each other.
+type MirrorKey = (Money, Option<Money>, Option<ActivityId>, Option<TransferId>);
+
+impl LedgerAudit {
+    /// Audit `activities`, expected to be the whole ledger.
+    ///
+    /// Activities between the same source and target accounts are paired in time order, first
+    /// with a mirror of the same amounts and transfer id, then with any other remaining half,
+    /// which is reported as a mismatch. Halves left without a counterpart, or owned by neither
+    /// of their accounts, are reported as orphans.
+    ///
+    /// Independently of pairing, withdrawals and deposits must sum to zero in every currency:
+    /// non-zero sums are reported as imbalances. Fails only if a sum overflows.
+    pub fn of(activities: Vec<Activity>) -> Result<Self, MoneyError> {
+        let activity_count = activities.len();
+        let mut orphans = vec![];
+        let mut sums: HashMap<Currency, Money> = HashMap::new();
+        let mut transfers: HashMap<(AccountId, AccountId), (Vec<Activity>, Vec<Activity>)> =
+            HashMap::new();
+
+        for activity in activities {
+            let is_withdrawal = activity.owner_account_id() == activity.source_account_id();
+            if !is_withdrawal && activity.owner_account_id() != activity.target_account_id() {
+                orphans.push(activity);
+                continue;
+            }
+
+            for money in std::iter::once(activity.money()).chain(activity.converted_money()) {
+                let sum = sums
+                    .entry(money.currency())
+                    .or_insert_with(|| Money::zero(money.currency()));
+                *sum = if is_withdrawal {
+                    (*sum - *money)?
+                } else {
+                    (*sum + *money)?
+                };
+            }
+
+            let halves = transfers
+                .entry((*activity.source_account_id(), *activity.target_account_id()))
+                .or_default();
+            if is_withdrawal {
+                halves.0.push(activity);
+            } else {
+                halves.1.push(activity);
+            }
+        }
+
+        let mut pair_count = 0;
+        let mut mismatches = vec![];
+        for (_, (withdrawals, deposits)) in transfers {
+            let (pairs, unpaired_withdrawals, unpaired_deposits) = pair(withdrawals, deposits);
+            pair_count += pairs;
+
+            let mut unpaired_withdrawals = unpaired_withdrawals.into_iter();
+            let mut unpaired_deposits = unpaired_deposits.into_iter();
+            loop {
+                match (unpaired_withdrawals.next(), unpaired_deposits.next()) {
+                    (Some(withdrawal), Some(deposit)) => mismatches.push(LedgerMismatch {
+                        withdrawal,
+                        deposit,
+                    }),
+                    (Some(orphan), None) | (None, Some(orphan)) => orphans.push(orphan),
+                    (None, None) => break,
+                }
+            }
+        }
+
+        orphans.sort_by_key(|a| a.id().map(|id| id.0));
+        mismatches.sort_by_key(|m| m.withdrawal.id().map(|id| id.0));
+        let mut imbalances: Vec<Money> =
+            sums.into_values().filter(|sum| sum.amount() != 0).collect();
+        imbalances.sort_by_key(|sum| sum.currency().code());
+
+        Ok(Self {
+            activity_count,
+            pair_count,
+            orphans,
+            mismatches,
+            imbalances,
+        })
+    }
+
+    /// Whether every activity is mirrored with the same amounts, and the ledger balanced.
+    pub fn is_consistent(&self) -> bool {
+        self.orphans.is_empty() && self.mismatches.is_empty() && self.imbalances.is_empty()
+    }
+
+    /// Get the number of audited activities.
+    pub fn activity_count(&self) -> usize {
+        self.activity_count
+    }
+
+    /// Get the number of transfers whose halves mirror each other.
+    pub fn pair_count(&self) -> usize {
+        self.pair_count
+    }
+
+    /// Get a reference to the activities without a counterpart.
+    pub fn orphans(&self) -> &[Activity] {
+        self.orphans.as_slice()
+    }
+
+    /// Get a reference to the halves of transfers recorded with different amounts.
+    pub fn mismatches(&self) -> &[LedgerMismatch] {
+        self.mismatches.as_slice()
+    }
+
+    /// Get a reference to the non-zero sums of the ledger, one per currency.
+    pub fn imbalances(&self) -> &[Money] {
+        self.imbalances.as_slice()
+    }
+}
+
+/// Pair `withdrawals` and `deposits` of the same accounts mirroring each other, in time order,
+/// returning the number of pairs and the unpaired halves, in time order.
+fn pair(
+    mut withdrawals: Vec<Activity>,
+    mut deposits: Vec<Activity>,
+) -> (usize, Vec<Activity>, Vec<Activity>) {
+    let chronological = |a: &Activity| (*a.timestamp(), a.id().map(|id| id.0));
+    withdrawals.sort_by_key(chronological);
+    deposits.sort_by_key(chronological);
+
+    let mut mirrors: HashMap<MirrorKey, VecDeque<Activity>> = HashMap::new();
+    for deposit in deposits {
+        mirrors
+            .entry(mirror_key(&deposit))
+            .or_default()
+            .push_back(deposit);
+    }
+
+    let mut pairs = 0;
+    let mut unpaired_withdrawals = vec![];
+    for withdrawal in withdrawals {
+        match mirrors
+            .get_mut(&mirror_key(&withdrawal))
+            .and_then(VecDeque::pop_front)
+        {
+            Some(_) => pairs += 1,
+            None => unpaired_withdrawals.push(withdrawal),
+        }
+    }
+
+    let mut unpaired_deposits: Vec<Activity> = mirrors.into_values().flatten().collect();
+    unpaired_deposits.sort_by_key(chronological);
+
+    (pairs, unpaired_withdrawals, unpaired_deposits)
+}
+
+fn mirror_key(activity: &Activity) -> MirrorKey {
+    (
+        *activity.money(),
+        activity.converted_money().copied(),
+        activity.reverses_activity_id().copied(),
+        activity.transfer_id().copied(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use crate::domain::{
+        activity::{tests::default_activity, ActivityBuilder},
+        money::tests::eur,
+    };
+
+    use super::*;
+
+    /// Half of a transfer of `money` from account `1` to account `2`, owned by `owner`.
+    fn half(id: u64, owner: u64, money: Money) -> ActivityBuilder {
+        default_activity()
+            .id(Some(ActivityId(id)))
+            .owner_account_id(AccountId(owner))
+            .source_account_id(AccountId(1))
+            .target_account_id(AccountId(2))
+            .timestamp(Utc.ymd(2021, 12, 12).and_hms(8, 0, 0) + Duration::seconds(id as i64))
+            .money(money)
+            .clone()
+    }
+
+    #[test]
+    fn mirrored_activities_are_consistent() {
+        // Given
+        let activities = vec![
+            half(1, 1, eur(500)).build().unwrap(),
+            half(2, 2, eur(500)).build().unwrap(),
+            half(3, 1, eur(200)).build().unwrap(),
+            half(4, 2, eur(200)).build().unwrap(),
+        ];
+
+        // When
+        let audit = LedgerAudit::of(activities).unwrap();
+
+        // Expect
+        assert!(audit.is_consistent());
+        assert_eq!(audit.activity_count(), 4);
+        assert_eq!(audit.pair_count(), 2);
+    }
+
+    #[test]
+    fn converted_mirrors_are_consistent() {
+        // Given
+        let usd = Money::new(543, Currency::USD);
+        let activities = vec![
+            half(1, 1, eur(500))
+                .converted_money(Some(usd))
+                .build()
+                .unwrap(),
+            half(2, 2, eur(500))
+                .converted_money(Some(usd))
+                .build()
+                .unwrap(),
+        ];
+
+        // When
+        let audit = LedgerAudit::of(activities).unwrap();
+
+        // Expect
+        assert!(audit.is_consistent());
+        assert_eq!(audit.pair_count(), 1);
+    }
+
+    #[test]
+    fn mirrors_are_paired_by_transfer_id() {
+        // Given
+        let activities = vec![
+            half(1, 1, eur(500))
+                .transfer_id(Some(TransferId(1)))
+                .build()
+                .unwrap(),
+            half(2, 1, eur(500))
+                .transfer_id(Some(TransferId(2)))
+                .build()
+                .unwrap(),
+            half(3, 2, eur(500))
+                .transfer_id(Some(TransferId(2)))
+                .build()
+                .unwrap(),
+        ];
+
+        // When
+        let audit = LedgerAudit::of(activities).unwrap();
+
+        // Expect
+        assert_eq!(audit.pair_count(), 1);
+        assert_eq!(audit.orphans()[0].id(), Some(&ActivityId(1)));
+    }
+
+    #[test]
+    fn unpaired_activities_are_orphans() {
+        // Given
+        let activities = vec![
+            half(1, 1, eur(500)).build().unwrap(),
+            half(2, 1, eur(200)).build().unwrap(),
+            half(3, 2, eur(200)).build().unwrap(),
+            half(4, 3, eur(100)).build().unwrap(),
+        ];
+
+        // When
+        let audit = LedgerAudit::of(activities).unwrap();
+
+        // Expect
+        assert!(!audit.is_consistent());
+        assert_eq!(audit.pair_count(), 1);
+        let orphans: Vec<_> = audit.orphans().iter().map(|a| a.id().copied()).collect();
+        assert_eq!(orphans, vec![Some(ActivityId(1)), Some(ActivityId(4))]);
+        assert!(audit.mismatches().is_empty());
+        assert_eq!(audit.imbalances(), &[eur(-500)]);
+    }
+
+    #[test]
+    fn halves_of_different_amounts_are_mismatches() {
+        // Given
+        let activities = vec![
+            half(1, 1, eur(500)).build().unwrap(),
+            half(2, 2, eur(200)).build().unwrap(),
+        ];
+
+        // When
+        let audit = LedgerAudit::of(activities).unwrap();
+
+        // Expect
+        assert_eq!(audit.pair_count(), 0);
+        assert!(audit.orphans().is_empty());
+        assert_eq!(audit.mismatches().len(), 1);
+        assert_eq!(*audit.mismatches()[0].withdrawal().money(), eur(500));
+        assert_eq!(*audit.mismatches()[0].deposit().money(), eur(200));
+        assert_eq!(audit.imbalances(), &[eur(-300)]);
+    }
+}
@@ -1,3 +1,8 @@
 pub mod account;
 pub mod activity;
+pub mod customer;
+pub mod exchange_rate;
+pub mod fee;
+pub mod ledger;
 pub mod money;
+pub mod scheduled_transfer;
@@ -1,14 +1,104 @@
-use derive_more::{Add, AddAssign, Neg, Sub, SubAssign};
+use std::{
+    cmp::Ordering,
+    fmt,
+    iter::Sum,
+    ops::{Add, Sub},
+    str::FromStr,
+};
 
-#[derive(
-    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Add, AddAssign, Neg, Sub, SubAssign,
-)]
+use derive_more::{Display, Error};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-pub struct Money(pub i64);
+/// ISO 4217 currency, along with the number of digits of its minor unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display)]
+#[display(fmt = "{}", code)]
+pub struct Currency {
+    code: &'static str,
+    scale: u8,
+}
+
+impl Currency {
+    pub const EUR: Currency = Currency::new("EUR", 2);
+    pub const USD: Currency = Currency::new("USD", 2);
+    pub const GBP: Currency = Currency::new("GBP", 2);
+    pub const CHF: Currency = Currency::new("CHF", 2);
+    pub const JPY: Currency = Currency::new("JPY", 0);
+
+    /// Currencies supported by the application.
+    pub const ALL: [Currency; 5] = [
+        Currency::EUR,
+        Currency::USD,
+        Currency::GBP,
+        Currency::CHF,
+        Currency::JPY,
+    ];
+
+    const fn new(code: &'static str, scale: u8) -> Self {
+        Self { code, scale }
+    }
+
+    /// Find a supported currency by its ISO 4217 alphabetic code.
+    pub fn from_code(code: &str) -> Option<Currency> {
+        Self::ALL
+            .iter()
+            .find(|currency| currency.code.eq_ignore_ascii_case(code.trim()))
+            .copied()
+    }
+
+    /// Get the currency's ISO 4217 alphabetic code.
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    /// Get the number of digits of the currency's minor unit.
+    pub fn scale(&self) -> u8 {
+        self.scale
+    }
+}
+
+/// Amount of money, in minor units (e.g. cents) of its currency.
+///
+/// Amounts of different currencies cannot be added, subtracted or compared. Arithmetic is
+/// checked: operators return an error rather than overflowing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Money {
+    amount: i64,
+    currency: Currency,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Display, Error)]
+pub enum MoneyError {
+    #[display(fmt = "Currency mismatch: expected {}, got {}", expected, actual)]
+    CurrencyMismatch {
+        expected: Currency,
+        actual: Currency,
+    },
+    #[display(fmt = "Arithmetic overflow on {} amounts", _0)]
+    Overflow(#[error(not(source))] Currency),
+    #[display(fmt = "Cannot sum an empty sequence of amounts without a currency")]
+    EmptySum,
+}
 
 impl Money {
+    pub const fn new(amount: i64, currency: Currency) -> Self {
+        Self { amount, currency }
+    }
+
+    pub const fn zero(currency: Currency) -> Self {
+        Self::new(0, currency)
+    }
+
+    /// Get the amount, in minor units of the currency.
+    pub fn amount(&self) -> i64 {
+        self.amount
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
     pub fn is_positive_or_zero(&self) -> bool {
-        self.0 >= 0
+        self.amount >= 0
     }
 
     pub fn is_negative(&self) -> bool {
@@ -16,41 +106,357 @@ impl Money {
     }
 
     pub fn is_positive(&self) -> bool {
-        self.0 > 0
+        self.amount > 0
+    }
+
+    /// Fail unless `other` is of the same currency.
+    pub fn ensure_same_currency(&self, other: &Money) -> Result<(), MoneyError> {
+        if self.currency != other.currency {
+            return Err(MoneyError::CurrencyMismatch {
+                expected: self.currency,
+                actual: other.currency,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn checked_add(self, rhs: Money) -> Result<Money, MoneyError> {
+        self.ensure_same_currency(&rhs)?;
+        self.with_amount(self.amount.checked_add(rhs.amount))
+    }
+
+    pub fn checked_sub(self, rhs: Money) -> Result<Money, MoneyError> {
+        self.ensure_same_currency(&rhs)?;
+        self.with_amount(self.amount.checked_sub(rhs.amount))
+    }
+
+    pub fn checked_neg(self) -> Result<Money, MoneyError> {
+        self.with_amount(self.amount.checked_neg())
+    }
+
+    /// Add `rhs`, clamping the amount at the numeric bounds instead of overflowing.
+    pub fn saturating_add(self, rhs: Money) -> Result<Money, MoneyError> {
+        self.ensure_same_currency(&rhs)?;
+        Ok(Money::new(
+            self.amount.saturating_add(rhs.amount),
+            self.currency,
+        ))
+    }
+
+    /// Subtract `rhs`, clamping the amount at the numeric bounds instead of overflowing.
+    pub fn saturating_sub(self, rhs: Money) -> Result<Money, MoneyError> {
+        self.ensure_same_currency(&rhs)?;
+        Ok(Money::new(
+            self.amount.saturating_sub(rhs.amount),
+            self.currency,
+        ))
+    }
+
+    fn with_amount(self, amount: Option<i64>) -> Result<Money, MoneyError> {
+        amount
+            .map(|amount| Money::new(amount, self.currency))
+            .ok_or(MoneyError::Overflow(self.currency))
+    }
+}
+
+impl Add for Money {
+    type Output = Result<Money, MoneyError>;
+
+    fn add(self, rhs: Money) -> Self::Output {
+        self.checked_add(rhs)
+    }
+}
+
+impl Sub for Money {
+    type Output = Result<Money, MoneyError>;
+
+    fn sub(self, rhs: Money) -> Self::Output {
+        self.checked_sub(rhs)
+    }
+}
+
+/// Sum amounts of a single currency, failing on currency mismatch or overflow.
+///
+/// An empty sequence fails as its currency is unknown: chain a `Money::zero` to sum possibly
+/// empty sequences.
+impl Sum<Money> for Result<Money, MoneyError> {
+    fn sum<I: Iterator<Item = Money>>(mut iter: I) -> Self {
+        let first = iter.next().ok_or(MoneyError::EmptySum)?;
+        iter.try_fold(first, Money::checked_add)
+    }
+}
+
+impl<'a> Sum<&'a Money> for Result<Money, MoneyError> {
+    fn sum<I: Iterator<Item = &'a Money>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl PartialOrd for Money {
+    /// Amounts of different currencies are not comparable.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.currency != other.currency {
+            return None;
+        }
+
+        Some(self.amount.cmp(&other.amount))
+    }
+}
+
+/// Formats as a decimal amount followed by the currency code, e.g. `12.34 EUR`.
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.to_decimal_string(), self.currency)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Display, Error)]
+pub enum ParseMoneyError {
+    #[display(fmt = "Invalid money '{}': expected an amount and a currency code", _0)]
+    InvalidFormat(#[error(not(source))] String),
+    #[display(fmt = "Invalid amount '{}'", _0)]
+    InvalidAmount(#[error(not(source))] String),
+    #[display(fmt = "Unsupported currency '{}'", _0)]
+    UnsupportedCurrency(#[error(not(source))] String),
+    #[display(fmt = "Amount has more decimal digits than {} allows", _0)]
+    TooPrecise(#[error(not(source))] Currency),
+    #[display(fmt = "Amount overflows {} amounts", _0)]
+    Overflow(#[error(not(source))] Currency),
+}
+
+impl Money {
+    /// Format the amount in major units of the currency, e.g. `12.34` for 1234 euro cents.
+    pub fn to_decimal_string(&self) -> String {
+        let scale = self.currency.scale as usize;
+        if scale == 0 {
+            return self.amount.to_string();
+        }
+
+        let divisor = 10u64.pow(scale as u32);
+        let magnitude = self.amount.unsigned_abs();
+        let sign = if self.amount < 0 { "-" } else { "" };
+
+        format!(
+            "{}{}.{:0width$}",
+            sign,
+            magnitude / divisor,
+            magnitude % divisor,
+            width = scale
+        )
+    }
+
+    /// Parse an amount in major units of `currency`, e.g. `12.34`, rejecting digits beyond
+    /// the currency's minor unit.
+    pub fn from_decimal_str(amount: &str, currency: Currency) -> Result<Money, ParseMoneyError> {
+        parse_fixed_point(amount, currency.scale)
+            .map(|amount| Money::new(amount, currency))
+            .map_err(|e| match e {
+                FixedPointError::Invalid => {
+                    ParseMoneyError::InvalidAmount(amount.trim().to_owned())
+                }
+                FixedPointError::TooPrecise => ParseMoneyError::TooPrecise(currency),
+                FixedPointError::Overflow => ParseMoneyError::Overflow(currency),
+            })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum FixedPointError {
+    Invalid,
+    TooPrecise,
+    Overflow,
+}
+
+/// Parse a decimal number, e.g. `12.34`, as an integer of `scale` implied decimal digits,
+/// e.g. `1234` for a scale of 2.
+pub(super) fn parse_fixed_point(number: &str, scale: u8) -> Result<i64, FixedPointError> {
+    let number = number.trim();
+
+    let (sign, digits) = match number.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", number.strip_prefix('+').unwrap_or(number)),
+    };
+    let (integer, fraction) = match digits.split_once('.') {
+        Some((_, "")) => return Err(FixedPointError::Invalid),
+        Some((integer, fraction)) => (integer, fraction),
+        None => (digits, ""),
+    };
+
+    let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if integer.is_empty() || !is_digits(integer) || !is_digits(fraction) {
+        return Err(FixedPointError::Invalid);
+    }
+
+    let scale = scale as usize;
+    if fraction.len() > scale {
+        return Err(FixedPointError::TooPrecise);
+    }
+
+    // Digits are validated above, so parsing may only fail on overflow
+    format!("{}{}{:0<scale$}", sign, integer, fraction, scale = scale)
+        .parse()
+        .map_err(|_| FixedPointError::Overflow)
+}
+
+/// Parses a decimal amount followed by the currency code, e.g. `12.34 EUR`.
+impl FromStr for Money {
+    type Err = ParseMoneyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let (amount, code) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(amount), Some(code), None) => (amount, code),
+            _ => return Err(ParseMoneyError::InvalidFormat(s.to_owned())),
+        };
+
+        let currency = Currency::from_code(code)
+            .ok_or_else(|| ParseMoneyError::UnsupportedCurrency(code.to_owned()))?;
+
+        Money::from_decimal_str(amount, currency)
+    }
+}
+
+/// Serializes as a decimal string, e.g. `"12.34 EUR"`. Use [`minor_units`] to serialize as
+/// minor units instead.
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserializes from either a decimal string, e.g. `"12.34 EUR"`, or minor units, e.g.
+/// `{ "amount": 1234, "currency": "EUR" }`.
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Decimal(String),
+            MinorUnits { amount: i64, currency: String },
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Decimal(money) => money.parse().map_err(D::Error::custom),
+            Repr::MinorUnits { amount, currency } => Currency::from_code(&currency)
+                .map(|currency| Money::new(amount, currency))
+                .ok_or_else(|| D::Error::custom(ParseMoneyError::UnsupportedCurrency(currency))),
+        }
+    }
+}
+
+/// Serde representation of `Money` as minor units, e.g. `{ "amount": 1234, "currency": "EUR" }`,
+/// to be used with `#[serde(with = "money::minor_units")]`.
+pub mod minor_units {
+    use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serializer};
+
+    use super::Money;
+
+    pub fn serialize<S: Serializer>(money: &Money, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Money", 2)?;
+        state.serialize_field("amount", &money.amount)?;
+        state.serialize_field("currency", money.currency.code)?;
+        state.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Money, D::Error> {
+        Money::deserialize(deserializer)
     }
 }
 
 #[cfg(test)]
-mod tests {
+pub mod tests {
     use super::*;
 
+    /// Get `amount` euro cents.
+    pub fn eur(amount: i64) -> Money {
+        Money::new(amount, Currency::EUR)
+    }
+
     #[test]
     fn test_money_add() {
         // Given
-        let m = Money(10);
-        let n = Money(5);
+        let m = eur(10);
+        let n = eur(5);
 
         // Expect
-        assert_eq!(m + n, Money(15));
-        assert_eq!(n + m, Money(15));
+        assert_eq!(m + n, Ok(eur(15)));
+        assert_eq!(n + m, Ok(eur(15)));
     }
 
     #[test]
-    fn test_money_addassign() {
+    fn test_money_sub() {
         // Given
-        let mut m = Money(10);
-        let n = Money(5);
-        // When
-        m += n;
+        let m = eur(10);
+        let n = eur(5);
+
+        // Expect
+        assert_eq!(m - n, Ok(eur(5)));
+        assert_eq!(n - m, Ok(eur(-5)));
+    }
+
+    #[test]
+    fn test_money_currency_mismatch() {
+        // Given
+        let m = eur(10);
+        let n = Money::new(5, Currency::USD);
+
+        // Expect
+        let mismatch = Err(MoneyError::CurrencyMismatch {
+            expected: Currency::EUR,
+            actual: Currency::USD,
+        });
+        assert_eq!(m + n, mismatch);
+        assert_eq!(m - n, mismatch);
+    }
+
+    #[test]
+    fn test_money_overflow() {
+        // Given
+        let max = eur(i64::MAX);
+        let min = eur(i64::MIN);
+
         // Expect
-        assert_eq!(m, Money(15));
+        assert_eq!(max + eur(1), Err(MoneyError::Overflow(Currency::EUR)));
+        assert_eq!(min - eur(1), Err(MoneyError::Overflow(Currency::EUR)));
+        assert_eq!(min.checked_neg(), Err(MoneyError::Overflow(Currency::EUR)));
+        assert_eq!(eur(5).checked_neg(), Ok(eur(-5)));
+    }
+
+    #[test]
+    fn test_money_saturating() {
+        // Expect
+        assert_eq!(eur(i64::MAX).saturating_add(eur(1)), Ok(eur(i64::MAX)));
+        assert_eq!(eur(i64::MIN).saturating_sub(eur(1)), Ok(eur(i64::MIN)));
+        assert!(eur(1).saturating_add(Money::new(1, Currency::USD)).is_err());
+    }
+
+    #[test]
+    fn test_money_sum() {
+        // Given
+        let amounts = [eur(1), eur(2), eur(3)];
+
+        // Expect
+        assert_eq!(amounts.iter().sum::<Result<Money, _>>(), Ok(eur(6)));
+        assert_eq!(
+            vec![eur(i64::MAX), eur(1)]
+                .into_iter()
+                .sum::<Result<Money, _>>(),
+            Err(MoneyError::Overflow(Currency::EUR))
+        );
+        assert_eq!(
+            Vec::<Money>::new().into_iter().sum::<Result<Money, _>>(),
+            Err(MoneyError::EmptySum)
+        );
     }
 
     #[test]
     fn test_is_cmp() {
         // Given
-        let m = Money(10);
-        let n = Money(5);
+        let m = eur(10);
+        let n = eur(5);
 
         // Expect
         assert!(m > n);
@@ -59,4 +465,102 @@ mod tests {
         assert!(m == m);
         assert!(m != n);
     }
+
+    #[test]
+    fn test_different_currencies_are_not_comparable() {
+        // Given
+        let m = eur(10);
+        let n = Money::new(10, Currency::USD);
+
+        // Expect
+        assert_eq!(m.partial_cmp(&n), None);
+        assert!(m != n);
+    }
+
+    #[test]
+    fn test_money_display() {
+        assert_eq!(eur(1234).to_string(), "12.34 EUR");
+        assert_eq!(eur(5).to_string(), "0.05 EUR");
+        assert_eq!(eur(-1205).to_string(), "-12.05 EUR");
+        assert_eq!(eur(i64::MIN).to_string(), "-92233720368547758.08 EUR");
+        assert_eq!(Money::new(1234, Currency::JPY).to_string(), "1234 JPY");
+    }
+
+    #[test]
+    fn test_money_from_str() {
+        assert_eq!("12.34 EUR".parse(), Ok(eur(1234)));
+        assert_eq!("12.3 eur".parse(), Ok(eur(1230)));
+        assert_eq!("-12 EUR".parse(), Ok(eur(-1200)));
+        assert_eq!("1234 JPY".parse(), Ok(Money::new(1234, Currency::JPY)));
+        assert_eq!("-92233720368547758.08 EUR".parse(), Ok(eur(i64::MIN)));
+    }
+
+    #[test]
+    fn test_money_from_str_failures() {
+        assert!(matches!(
+            "12.34".parse::<Money>(),
+            Err(ParseMoneyError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            "12.34 XXX".parse::<Money>(),
+            Err(ParseMoneyError::UnsupportedCurrency(_))
+        ));
+        for amount in &["", ".5", "5.", "1.2.3", "1,5", "--1", "1e3"] {
+            assert!(matches!(
+                Money::from_decimal_str(amount, Currency::EUR),
+                Err(ParseMoneyError::InvalidAmount(_))
+            ));
+        }
+        assert_eq!(
+            "12.345 EUR".parse::<Money>(),
+            Err(ParseMoneyError::TooPrecise(Currency::EUR))
+        );
+        assert_eq!(
+            "1.5 JPY".parse::<Money>(),
+            Err(ParseMoneyError::TooPrecise(Currency::JPY))
+        );
+        assert_eq!(
+            "92233720368547758.08 EUR".parse::<Money>(),
+            Err(ParseMoneyError::Overflow(Currency::EUR))
+        );
+    }
+
+    #[test]
+    fn test_money_serde() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Transfer {
+            money: Money,
+            #[serde(with = "minor_units")]
+            fee: Money,
+        }
+
+        // Given
+        let transfer = Transfer {
+            money: eur(1234),
+            fee: eur(5),
+        };
+
+        // When
+        let json = serde_json::to_string(&transfer).unwrap();
+
+        // Expect
+        assert_eq!(
+            json,
+            r#"{"money":"12.34 EUR","fee":{"amount":5,"currency":"EUR"}}"#
+        );
+        assert_eq!(serde_json::from_str::<Transfer>(&json).unwrap(), transfer);
+        assert_eq!(
+            serde_json::from_str::<Money>(r#"{"amount":1234,"currency":"EUR"}"#).unwrap(),
+            eur(1234)
+        );
+        assert!(serde_json::from_str::<Money>(r#""12.345 EUR""#).is_err());
+    }
+
+    #[test]
+    fn test_currency_from_code() {
+        assert_eq!(Currency::from_code("EUR"), Some(Currency::EUR));
+        assert_eq!(Currency::from_code("jpy"), Some(Currency::JPY));
+        assert_eq!(Currency::JPY.scale(), 0);
+        assert_eq!(Currency::from_code("XXX"), None);
+    }
 }
@@ -0,0 +1,233 @@
+use chrono::{DateTime, Utc};
+use derive_more::Display;
+
+use super::{account::AccountId, money::Money};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ScheduledTransferId(pub u64);
+
+/// Progress of a scheduled transfer, from `Pending` to either `Executed` or `Failed`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Display)]
+pub enum ScheduledTransferStatus {
+    #[default]
+    #[display(fmt = "pending")]
+    Pending,
+    /// Claimed by a worker, which is sending the money.
+    #[display(fmt = "executing")]
+    Executing,
+    #[display(fmt = "executed")]
+    Executed,
+    #[display(fmt = "failed")]
+    Failed,
+}
+
+impl ScheduledTransferStatus {
+    pub const ALL: [ScheduledTransferStatus; 4] = [
+        ScheduledTransferStatus::Pending,
+        ScheduledTransferStatus::Executing,
+        ScheduledTransferStatus::Executed,
+        ScheduledTransferStatus::Failed,
+    ];
+
+    /// Find the status persisted as `code`.
+    pub fn from_code(code: &str) -> Option<ScheduledTransferStatus> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|status| status.code() == code)
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            ScheduledTransferStatus::Pending => "pending",
+            ScheduledTransferStatus::Executing => "executing",
+            ScheduledTransferStatus::Executed => "executed",
+            ScheduledTransferStatus::Failed => "failed",
+        }
+    }
+}
+
+/// Transfer of money to execute once `execute_at` has passed.
+#[derive(Debug, Clone, Builder)]
+pub struct ScheduledTransfer {
+    #[builder(default = "None")]
+    id: Option<ScheduledTransferId>,
+    source_account_id: AccountId,
+    target_account_id: AccountId,
+    money: Money,
+    execute_at: DateTime<Utc>,
+    #[builder(default)]
+    status: ScheduledTransferStatus,
+    /// When a worker last claimed the transfer.
+    #[builder(default = "None")]
+    claimed_at: Option<DateTime<Utc>>,
+    #[builder(default = "None")]
+    executed_at: Option<DateTime<Utc>>,
+    /// Why the transfer failed, once it has.
+    #[builder(default = "None")]
+    failure: Option<String>,
+}
+
+impl ScheduledTransfer {
+    pub fn new(
+        source_account_id: AccountId,
+        target_account_id: AccountId,
+        money: Money,
+        execute_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: None,
+            source_account_id,
+            target_account_id,
+            money,
+            execute_at,
+            status: ScheduledTransferStatus::Pending,
+            claimed_at: None,
+            executed_at: None,
+            failure: None,
+        }
+    }
+
+    pub fn with_id(self, id: ScheduledTransferId) -> Self {
+        Self {
+            id: Some(id),
+            ..self
+        }
+    }
+
+    /// Whether the transfer is still pending and its execution time has passed at `now`.
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.status == ScheduledTransferStatus::Pending && self.execute_at <= now
+    }
+
+    /// Whether the transfer was claimed before `claimed_before` and is still executing, its
+    /// worker having presumably stopped before recording the outcome.
+    pub fn is_claim_expired(&self, claimed_before: DateTime<Utc>) -> bool {
+        self.status == ScheduledTransferStatus::Executing
+            && self
+                .claimed_at
+                .is_none_or(|claimed_at| claimed_at < claimed_before)
+    }
+
+    /// Record that a worker claimed the transfer at `claimed_at` and is sending the money.
+    pub fn mark_executing(&mut self, claimed_at: DateTime<Utc>) {
+        self.status = ScheduledTransferStatus::Executing;
+        self.claimed_at = Some(claimed_at);
+    }
+
+    /// Record that the money was sent at `executed_at`.
+    pub fn mark_executed(&mut self, executed_at: DateTime<Utc>) {
+        self.status = ScheduledTransferStatus::Executed;
+        self.executed_at = Some(executed_at);
+        self.failure = None;
+    }
+
+    /// Record that sending the money failed at `executed_at` because of `failure`.
+    pub fn mark_failed(&mut self, executed_at: DateTime<Utc>, failure: String) {
+        self.status = ScheduledTransferStatus::Failed;
+        self.executed_at = Some(executed_at);
+        self.failure = Some(failure);
+    }
+
+    /// Get a reference to the scheduled transfer's id.
+    pub fn id(&self) -> Option<&ScheduledTransferId> {
+        self.id.as_ref()
+    }
+
+    /// Get a reference to the scheduled transfer's source account id.
+    pub fn source_account_id(&self) -> &AccountId {
+        &self.source_account_id
+    }
+
+    /// Get a reference to the scheduled transfer's target account id.
+    pub fn target_account_id(&self) -> &AccountId {
+        &self.target_account_id
+    }
+
+    /// Get a reference to the scheduled transfer's money.
+    pub fn money(&self) -> &Money {
+        &self.money
+    }
+
+    /// Get a reference to the time after which the transfer is executed.
+    pub fn execute_at(&self) -> &DateTime<Utc> {
+        &self.execute_at
+    }
+
+    /// Get the scheduled transfer's status.
+    pub fn status(&self) -> ScheduledTransferStatus {
+        self.status
+    }
+
+    /// Get a reference to the time a worker last claimed the transfer.
+    pub fn claimed_at(&self) -> Option<&DateTime<Utc>> {
+        self.claimed_at.as_ref()
+    }
+
+    /// Get a reference to the time the transfer was executed or failed.
+    pub fn executed_at(&self) -> Option<&DateTime<Utc>> {
+        self.executed_at.as_ref()
+    }
+
+    /// Get a reference to the reason the transfer failed.
+    pub fn failure(&self) -> Option<&str> {
+        self.failure.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use crate::domain::money::tests::eur;
+
+    use super::*;
+
+    #[test]
+    fn pending_transfers_are_due_once_their_time_has_passed() {
+        // Given
+        let now = Utc::now();
+        let mut transfer = ScheduledTransfer::new(AccountId(1), AccountId(2), eur(100), now);
+
+        // Expect
+        assert!(!transfer.is_due(now - Duration::seconds(1)));
+        assert!(transfer.is_due(now));
+
+        // When
+        transfer.mark_failed(now, "Insufficient funds".into());
+
+        // Expect
+        assert!(!transfer.is_due(now));
+        assert_eq!(transfer.status(), ScheduledTransferStatus::Failed);
+        assert_eq!(transfer.failure(), Some("Insufficient funds"));
+    }
+
+    #[test]
+    fn executing_transfers_are_claimable_again_once_their_claim_expired() {
+        // Given
+        let now = Utc::now();
+        let mut transfer = ScheduledTransfer::new(AccountId(1), AccountId(2), eur(100), now);
+
+        // Expect
+        assert!(!transfer.is_claim_expired(now));
+
+        // When
+        transfer.mark_executing(now);
+
+        // Expect
+        assert!(!transfer.is_due(now));
+        assert!(!transfer.is_claim_expired(now));
+        assert!(transfer.is_claim_expired(now + Duration::seconds(1)));
+        assert_eq!(transfer.claimed_at(), Some(&now));
+    }
+
+    #[test]
+    fn status_codes_round_trip() {
+        for status in ScheduledTransferStatus::ALL {
+            assert_eq!(
+                ScheduledTransferStatus::from_code(status.code()),
+                Some(status)
+            );
+        }
+    }
+}
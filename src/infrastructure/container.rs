@@ -1,12 +1,29 @@
-use shaku::ModuleBuilder;
+use std::sync::Arc;
+
+use rocket::{fairing, figment::Figment, Build, Rocket};
+use shaku::{HasComponent, ModuleBuilder};
 use sqlx::{postgres::PgPoolOptions, PgPool};
 
 use crate::{
-    adapter::output::AccountRepository,
-    application::{HelloWorldUseCaseImpl, PingPongUseCaseImpl, SendMoneyService},
+    adapter::output::{
+        AccountRepository, ActivityRepository, CreateAccountRepository, CustomerRepository,
+        InMemoryAccountLocks, LoadActivityRepository, LoadTransferRepository, PostgresIdGenerator,
+        PostgresUnitOfWorkPort, PostgresUnitOfWorkPortParameters, ScheduledTransferRepository,
+        StaticExchangeRateAdapter,
+    },
+    application::{
+        CloseAccountService, CreateAccountService, ExecuteScheduledTransfersService,
+        GetAccountBalanceService, GetCustomerAccountsService, HelloWorldUseCaseImpl,
+        LedgerAuditService, MoneyTransferPropertiesImpl, OpenAccountService, PingPongUseCaseImpl,
+        ReverseTransferService, ScheduleTransferService, SendMoneyBatchService, SendMoneyService,
+    },
 };
 
-use super::db::{DataSourceImpl, DataSourceImplParameters};
+use super::{
+    config::{AppConfig, DatabaseConfig},
+    db::{DataSourceImpl, DataSourceImplParameters},
+    scheduler::spawn_scheduled_transfers_worker,
+};
 
 pub type Inject<'r, I> = shaku_rocket::Inject<'r, HexagonalRocketModule, I>;
 
@@ -16,7 +33,27 @@ shaku::module! {
                       HelloWorldUseCaseImpl,
                       DataSourceImpl,
                       SendMoneyService,
-                      AccountRepository],
+                      SendMoneyBatchService,
+                      MoneyTransferPropertiesImpl,
+                      GetAccountBalanceService,
+                      GetCustomerAccountsService,
+                      LedgerAuditService,
+                      OpenAccountService,
+                      CloseAccountService,
+                      ReverseTransferService,
+                      CreateAccountService,
+                      ScheduleTransferService,
+                      ExecuteScheduledTransfersService,
+                      AccountRepository,
+                      ActivityRepository,
+                      LoadActivityRepository,
+                      LoadTransferRepository,
+                      CreateAccountRepository,
+                      CustomerRepository,
+                      PostgresIdGenerator,
+                      ScheduledTransferRepository,
+                      PostgresUnitOfWorkPort,
+                      StaticExchangeRateAdapter],
 
         providers = []
     }
@@ -27,13 +64,73 @@ pub async fn default_module(db_pool: PgPool) -> ModuleBuilder<HexagonalRocketMod
         .with_component_parameters::<DataSourceImpl>(DataSourceImplParameters { pool: db_pool })
 }
 
-pub async fn connect_db(uri: &str) -> sqlx::PgPool {
+/// Get a `ModuleBuilder` set up according to `config`.
+pub async fn configured_module(
+    db_pool: PgPool,
+    config: &AppConfig,
+) -> ModuleBuilder<HexagonalRocketModule> {
+    default_module(db_pool)
+        .await
+        .with_component_parameters::<MoneyTransferPropertiesImpl>(
+            config.money_transfer.clone().into(),
+        )
+        .with_component_parameters::<StaticExchangeRateAdapter>(
+            config.exchange_rates.rates.clone().into(),
+        )
+        .with_component_parameters::<PostgresUnitOfWorkPort>(PostgresUnitOfWorkPortParameters {
+            in_memory_account_locks: config
+                .features
+                .in_memory_account_lock
+                .then(|| Arc::new(InMemoryAccountLocks::default())),
+        })
+}
+
+pub async fn connect_db(config: &DatabaseConfig) -> Result<PgPool, sqlx::Error> {
     // Create a connection pool
     PgPoolOptions::new()
-        .max_connections(5)
-        .connect(uri)
+        .max_connections(config.max_connections)
+        .min_connections(config.min_connections)
+        .connect_timeout(config.connect_timeout())
+        .idle_timeout(config.idle_timeout())
+        .connect(&config.url())
         .await
-        .expect("Unable to connect to PostgreSQL")
+}
+
+/// Load the application configuration from `figment`, connect to PostgreSQL and build the
+/// resulting module, for commands run without launching Rocket.
+pub async fn build_module(figment: &Figment) -> anyhow::Result<HexagonalRocketModule> {
+    let config = AppConfig::from_figment(figment)?;
+    let db_pool = connect_db(&config.database).await?;
+
+    Ok(configured_module(db_pool, &config).await.build())
+}
+
+/// Load the application configuration, connect to PostgreSQL and manage the resulting module,
+/// aborting launch on failure.
+pub async fn configure_module(rocket: Rocket<Build>) -> fairing::Result {
+    let config = match AppConfig::from_figment(rocket.figment()) {
+        Ok(config) => config,
+        Err(err) => {
+            log::error!("Invalid configuration: {}", err);
+            return Err(rocket);
+        }
+    };
+
+    let db_pool = match connect_db(&config.database).await {
+        Ok(db_pool) => db_pool,
+        Err(err) => {
+            log::error!("Unable to connect to PostgreSQL: {}", err);
+            return Err(rocket);
+        }
+    };
+
+    let module = configured_module(db_pool, &config).await.build();
+
+    if config.scheduler.enabled {
+        spawn_scheduled_transfers_worker(module.resolve(), config.scheduler.clone());
+    }
+
+    Ok(rocket.manage(Box::new(module)))
 }
 
 #[cfg(test)]
@@ -43,9 +140,9 @@ pub mod tests {
     use rocket::tokio;
 
     use crate::{
-        application::port::output::LoadAccountPort,
+        application::port::output::{LoadAccountPort, PersistenceError},
         domain::account::{tests::default_account, AccountId},
-        infrastructure::tests::{self, testing_module},
+        infrastructure::tests::{self, mock_module},
     };
 
     use shaku::HasComponent;
@@ -60,15 +157,15 @@ pub mod tests {
             &self,
             _account_id: AccountId,
             _baseline_date: chrono::DateTime<Utc>,
-        ) -> anyhow::Result<crate::domain::account::Account> {
-            Ok(default_account().id(AccountId(43)).build()?)
+        ) -> Result<crate::domain::account::Account, PersistenceError> {
+            Ok(default_account().id(AccountId(43)).build().unwrap())
         }
     }
 
     #[tokio::test]
     async fn test_mock_load_account_port() -> Result<()> {
         tests::setup();
-        let module = testing_module()
+        let module = mock_module()
             .await
             .with_component_override::<dyn LoadAccountPort>(Box::new(MockLoadAccountPort()))
             .build();
@@ -1,5 +1,7 @@
+pub mod config;
 pub mod container;
 pub mod db;
+pub mod scheduler;
 
 #[cfg(test)]
 pub mod tests;
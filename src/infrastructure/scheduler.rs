@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use rocket::tokio::{self, task::JoinHandle};
+
+use crate::application::port::input::ExecuteScheduledTransfersUseCase;
+
+use super::config::SchedulerConfig;
+
+/// Spawn a task executing due scheduled transfers at every `config` interval, until the runtime
+/// shuts down.
+pub fn spawn_scheduled_transfers_worker(
+    execute_scheduled_transfers: Arc<dyn ExecuteScheduledTransfersUseCase>,
+    config: SchedulerConfig,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(config.interval());
+
+        loop {
+            interval.tick().await;
+
+            match execute_scheduled_transfers
+                .execute_due_transfers(Utc::now(), config.claim_timeout(), config.batch_size)
+                .await
+            {
+                Ok(0) => {}
+                Ok(n) => log::info!("Executed {} scheduled transfers", n),
+                Err(err) => log::error!("Unable to execute scheduled transfers: {}", err),
+            }
+        }
+    })
+}
@@ -1,7 +1,7 @@
 use anyhow::Result;
 use rocket::futures::TryStreamExt;
 use shaku::ModuleBuilder;
-use sqlx::{Executor, PgPool};
+use sqlx::{postgres::PgPoolOptions, Executor, PgPool};
 use std::{
     collections::HashMap,
     sync::{Arc, Once},
@@ -10,7 +10,10 @@ use testcontainers::*;
 use tokio::sync::OnceCell;
 use walkdir::{DirEntry, WalkDir};
 
-use crate::infrastructure::container::{connect_db, default_module, HexagonalRocketModule};
+use crate::infrastructure::{
+    config::DatabaseConfig,
+    container::{connect_db, default_module, HexagonalRocketModule},
+};
 
 /// Synchronization variable to run tests initialization only once, globally
 static TESTS_INIT: Once = Once::new();
@@ -51,12 +54,29 @@ pub async fn testing_module() -> ModuleBuilder<HexagonalRocketModule> {
 
     // Connect to Postgres testing container
     log::info!("Connecting to Postgres...");
-    let db_pool = connect_db(&uri).await;
+    let config = DatabaseConfig {
+        url: Some(uri),
+        ..Default::default()
+    };
+    let db_pool = connect_db(&config)
+        .await
+        .expect("Unable to connect to PostgreSQL");
     log::info!("Connected to Postgres...");
 
     default_module(db_pool).await
 }
 
+/// Get a `ModuleBuilder` for tests overriding every component reaching the database with mocks.
+///
+/// No Postgres instance is run: the pool never connects, so any actual query fails.
+pub async fn mock_module() -> ModuleBuilder<HexagonalRocketModule> {
+    let db_pool = PgPoolOptions::new()
+        .connect_lazy(&DatabaseConfig::default().url())
+        .expect("Invalid PostgreSQL URL");
+
+    default_module(db_pool).await
+}
+
 #[derive(Debug)]
 struct PgContainer<'d> {
     container: Container<'d, clients::Cli, images::postgres::Postgres>,
@@ -116,7 +136,13 @@ async fn migrate_db(uri: &str) {
                 .get_or_init(|| async {
                     let uri = uri.to_owned();
                     log::info!("Starting Postgres migration process...");
-                    let db_pool = connect_db(&uri).await;
+                    let config = DatabaseConfig {
+                        url: Some(uri),
+                        ..Default::default()
+                    };
+                    let db_pool = connect_db(&config)
+                        .await
+                        .expect("Unable to connect to PostgreSQL");
 
                     log::info!("Running Postgres migrations...");
                     sqlx::migrate!("./migrations").run(&db_pool).await.unwrap();
@@ -1,7 +1,9 @@
-insert into account (id)
-values (1);
-insert into account (id)
-values (2);
+insert into customer (id, name)
+values (1, 'Ada Lovelace');
+insert into account (id, customer_id)
+values (1, 1);
+insert into account (id, customer_id)
+values (2, 1);
 insert into activity (
         id,
         timestamp,
@@ -73,4 +75,7 @@ insert into activity (
         target_account_id,
         amount
     )
-values (8, '2019-08-09 10:00:00.0', 2, 2, 1, 1000);
\ No newline at end of file
+values (8, '2019-08-09 10:00:00.0', 2, 2, 1, 1000);
+select setval('activity_id_seq', (select max(id) from activity));
+select setval('account_id_seq', (select max(id) from account));
+select setval('customer_id_seq', (select max(id) from customer));